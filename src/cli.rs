@@ -1,37 +1,74 @@
 use crate::commands::{self, Command};
-use clap::{Parser, Subcommand};
+use crate::error::CommandError;
+use clap::{ArgAction, CommandFactory, Parser, Subcommand};
+use clap_complete::CompleteEnv;
 
 #[derive(Subcommand)]
 pub enum Commands {
+    Init(commands::Init),
     Add(commands::Add),
+    Adopt(commands::Adopt),
     List(commands::List),
     Update(commands::Update),
+    Edit(commands::Edit),
+    Merge(commands::Merge),
     Remove(commands::Remove),
+    Mv(commands::Mv),
     Remote(commands::Remote),
     Reset(commands::Reset),
     Clone(commands::Clone),
+    Show(commands::Show),
+    Fetch(commands::Fetch),
     Sync(commands::Sync),
+    Watch(commands::Watch),
+    Doctor(commands::Doctor),
+    Log(commands::Log),
+    Completions(commands::Completions),
+    Man(commands::Man),
+    Ui(commands::Ui),
 }
 
 /// dfmn - dotfiles Manager
 #[derive(Parser)]
-#[command(version)]
+#[command(version, after_long_help = commands::long_help())]
 pub struct CLI {
+    /// Shows what dfmn is doing, use it twice to also show the output of git
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    pub verbose: u8,
     #[command(subcommand)]
     pub command: Commands,
 }
 
 impl Commands {
-    pub fn invoke(self) {
+    /// Whether git and the storage folder must be ready before running the
+    /// command. doctor checks them itself to report what is wrong
+    pub fn needs_setup(&self) -> bool {
+        !matches!(self, Self::Doctor(_))
+    }
+
+    pub fn invoke(self) -> Result<(), CommandError> {
         match self {
+            Self::Init(cmd) => cmd.call(),
             Self::Add(cmd) => cmd.call(),
+            Self::Adopt(cmd) => cmd.call(),
             Self::List(cmd) => cmd.call(),
             Self::Update(cmd) => cmd.call(),
+            Self::Edit(cmd) => cmd.call(),
+            Self::Merge(cmd) => cmd.call(),
             Self::Remove(cmd) => cmd.call(),
+            Self::Mv(cmd) => cmd.call(),
             Self::Remote(cmd) => cmd.call(),
             Self::Reset(cmd) => cmd.call(),
             Self::Clone(cmd) => cmd.call(),
+            Self::Show(cmd) => cmd.call(),
+            Self::Fetch(cmd) => cmd.call(),
             Self::Sync(cmd) => cmd.call(),
+            Self::Watch(cmd) => cmd.call(),
+            Self::Doctor(cmd) => cmd.call(),
+            Self::Log(cmd) => cmd.call(),
+            Self::Completions(cmd) => cmd.call(),
+            Self::Man(cmd) => cmd.call(),
+            Self::Ui(cmd) => cmd.call(),
         }
     }
 }
@@ -39,3 +76,10 @@ impl Commands {
 pub fn parse() -> CLI {
     CLI::parse()
 }
+
+/// Answers the completion requests of the shells, exiting when it is one
+pub fn complete() {
+    CompleteEnv::with_factory(CLI::command)
+        .var(commands::COMPLETE_VAR)
+        .complete();
+}
@@ -1,20 +1,33 @@
 use super::Command;
+use crate::deploy;
+use crate::dfmnignore::IgnoreMatcher;
 use crate::error::{CommandError, ExecutionError};
 use crate::git::GitCommandExecuterBuilder;
+use crate::manifest::Manifest;
+use crate::message::MessageArgs;
+use crate::paths;
+use crate::permissions;
+use crate::state::State;
 use crate::utils;
-use clap::Args;
-use std::env;
-use std::fs;
+use clap::{Args, ValueHint};
+use clap_complete::{ArgValueCompleter, PathCompleter};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
+use walkdir::WalkDir;
 
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("File already added to the remote repository")]
     FileAlreadyAdded,
-    #[error("File does not exist in your current directory")]
+    #[error("File does not exist")]
     FileDoesNotExists,
-    #[error("You can just add files to the repository")]
+    #[error("You can just add files or folders to the repository")]
     NotAFile,
+    #[error("File is ignored by the .dfmnignore file")]
+    FileIgnored,
+    #[error("There are no files to add")]
+    NothingToAdd,
     #[error("You need to set a remote repository before use dfmn")]
     SetRemoteRepository,
     #[error("No internet connection")]
@@ -23,15 +36,92 @@ pub enum Error {
 
 impl From<Error> for CommandError {
     fn from(err: Error) -> Self {
-        CommandError::Usage(err.to_string())
+        match err {
+            Error::NoInternetConnection => CommandError::Network(err.to_string()),
+            err => CommandError::Usage(err.to_string()),
+        }
     }
 }
 
-/// Adds a file to the remote repository
+/// Adds files or folders to the remote repository
 #[derive(Debug, Args)]
 pub struct Add {
-    /// File name
-    name: String,
+    /// File or folder paths, accepts glob patterns
+    #[arg(required = true, value_hint = ValueHint::AnyPath, add = ArgValueCompleter::new(PathCompleter::any()))]
+    names: Vec<PathBuf>,
+    /// Tracks files outside of your home folder
+    #[arg(short, long)]
+    force: bool,
+    /// Records the modification time of the files to restore it when deploying them
+    #[arg(long)]
+    preserve_mtime: bool,
+    #[command(flatten)]
+    message: MessageArgs,
+    /// Takes the names as they are instead of expanding them as glob patterns
+    #[arg(skip)]
+    literal: bool,
+}
+
+impl Add {
+    /// Adds the given paths, used by the commands that pick the files for the
+    /// user, so they are never expanded as glob patterns
+    pub(super) fn new(paths: Vec<PathBuf>, force: bool, message: MessageArgs) -> Self {
+        Add {
+            names: paths,
+            force,
+            preserve_mtime: false,
+            message,
+            literal: true,
+        }
+    }
+}
+
+/// Returns the path and the repository name of every file inside a folder that is not ignored
+fn get_folder_files(
+    folder_path: &Path,
+    folder_name: &str,
+    ignore_matcher: &IgnoreMatcher,
+) -> Result<Vec<(PathBuf, String)>, ExecutionError> {
+    let mut files = Vec::new();
+
+    let walker = WalkDir::new(folder_path)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            let Ok(relative_path) = entry.path().strip_prefix(folder_path) else {
+                return true;
+            };
+
+            let Ok(name) = utils::path_to_name(&Path::new(folder_name).join(relative_path)) else {
+                return true;
+            };
+
+            !ignore_matcher.is_ignored(&name, entry.file_type().is_dir())
+        });
+
+    for entry in walker {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(err) => {
+                return Err(ExecutionError::GetDirEntry(err.to_string()));
+            }
+        };
+
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let Ok(relative_path) = entry.path().strip_prefix(folder_path) else {
+            continue;
+        };
+
+        let name = utils::path_to_name(&Path::new(folder_name).join(relative_path))?;
+
+        files.push((entry.path().to_path_buf(), name));
+    }
+
+    Ok(files)
 }
 
 impl Command for Add {
@@ -58,37 +148,85 @@ impl Command for Add {
             return Err(Error::SetRemoteRepository.into());
         }
 
-        let current_dir = match env::current_dir() {
-            Ok(path) => path,
-            Err(err) => {
-                return Err(ExecutionError::GetCurrentDir(err.to_string()).into());
+        let mut source_paths = Vec::new();
+        let mut seen = HashSet::new();
+
+        for name in &self.names {
+            match name.to_str() {
+                Some(pattern) if !self.literal && utils::is_glob_pattern(pattern) => {
+                    source_paths.extend(utils::expand_path_pattern(&paths::resolve_pattern(
+                        pattern,
+                    )?)?);
+                }
+                _ => source_paths.push(paths::resolve(name)?),
+            }
+        }
+
+        source_paths.retain(|path| seen.insert(path.clone()));
+
+        let ignore_matcher = IgnoreMatcher::load(&git_storage_folder_path)?;
+        let mut names = Vec::new();
+        let mut files = Vec::new();
+
+        for source_path in source_paths {
+            if !source_path.exists() {
+                return Err(Error::FileDoesNotExists.into());
+            }
+
+            if !source_path.is_file() && !source_path.is_dir() {
+                return Err(Error::NotAFile.into());
             }
-        };
 
-        if !utils::check_if_file_exists(&current_dir, &self.name) {
-            return Err(Error::FileDoesNotExists.into());
+            let name = paths::get_name(&source_path, self.force)?;
+
+            if ignore_matcher.is_ignored(&name, source_path.is_dir()) {
+                return Err(Error::FileIgnored.into());
+            }
+
+            if source_path.is_dir() {
+                files.extend(get_folder_files(&source_path, &name, &ignore_matcher)?);
+            } else {
+                files.push((source_path, name.clone()));
+            }
+
+            names.push(name);
         }
 
-        if !current_dir.join(&self.name).is_file() {
-            return Err(Error::NotAFile.into());
+        if files.is_empty() {
+            return Err(Error::NothingToAdd.into());
         }
 
-        if utils::check_if_file_exists(&git_storage_folder_path, &self.name) {
-            return Err(Error::FileAlreadyAdded.into());
+        for (_, name) in &files {
+            if utils::check_if_file_exists(&git_storage_folder_path, name) {
+                return Err(Error::FileAlreadyAdded.into());
+            }
         }
 
-        if let Err(err) = fs::copy(
-            current_dir.join(&self.name),
-            git_storage_folder_path.join(&self.name),
-        ) {
-            return Err(ExecutionError::CopyFile(err.to_string()).into());
+        let message = self.message.build("Add", &names)?;
+        let mut manifest = Manifest::load(&git_storage_folder_path)?;
+        let mut state = State::load()?;
+
+        for (path, name) in &files {
+            deploy::deploy_file(path, &git_storage_folder_path.join(name), None)?;
+            state.record(&git_storage_folder_path, name, path)?;
+
+            manifest.set_destination(name, path)?;
+            permissions::record(
+                manifest.files.entry(name.clone()).or_default(),
+                path,
+                self.preserve_mtime,
+            )?;
         }
 
+        manifest.save(&git_storage_folder_path)?;
+
         GitCommandExecuterBuilder::new(&git_storage_folder_path)
-            .run_commit(&format!("Add {}", self.name))
+            .run_commit(message)
             .build()
             .run()?;
 
-        Ok("Successfully added the file and synchronized the local repository with the remote repository".to_string())
+        state.save()?;
+
+        Ok("Successfully added the file(s) and synchronized the local repository with the remote repository".to_string())
     }
 }
@@ -0,0 +1,173 @@
+use super::{Add, Command};
+use crate::error::{CommandError, ExecutionError};
+use crate::message::MessageArgs;
+use crate::paths;
+use crate::utils;
+use clap::Args;
+use colored::Colorize;
+use std::collections::BTreeSet;
+use std::env;
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// Well-known configuration files and folders, relative to the home folder
+const HOME_CANDIDATES: &[&str] = &[
+    ".bashrc",
+    ".bash_profile",
+    ".bash_aliases",
+    ".profile",
+    ".zshrc",
+    ".zprofile",
+    ".zshenv",
+    ".inputrc",
+    ".gitconfig",
+    ".tmux.conf",
+    ".vimrc",
+];
+
+/// Well-known configuration files and folders, relative to the config folder
+const CONFIG_CANDIDATES: &[&str] = &[
+    "fish/config.fish",
+    "git/config",
+    "git/ignore",
+    "tmux/tmux.conf",
+    "nvim",
+    "starship.toml",
+    "alacritty/alacritty.toml",
+    "alacritty/alacritty.yml",
+];
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("No untracked well-known dotfiles were found")]
+    NothingToAdopt,
+    #[error("{0:?} is not one of the listed numbers")]
+    InvalidSelection(String),
+}
+
+impl From<Error> for CommandError {
+    fn from(err: Error) -> Self {
+        CommandError::Usage(err.to_string())
+    }
+}
+
+/// Finds well-known dotfiles that are not tracked yet and adds the ones you pick
+#[derive(Debug, Args)]
+pub struct Adopt {
+    /// Adds every file found without asking
+    #[arg(short, long)]
+    all: bool,
+    #[command(flatten)]
+    message: MessageArgs,
+}
+
+/// Returns the config folder, which is `$XDG_CONFIG_HOME` or `~/.config`
+fn get_config_folder_path() -> Result<PathBuf, ExecutionError> {
+    match env::var("XDG_CONFIG_HOME") {
+        Ok(path) if !path.is_empty() => Ok(PathBuf::from(path)),
+        _ => Ok(utils::get_home_folder_path()?.join(".config")),
+    }
+}
+
+/// Returns the well-known files and folders that exist and are not tracked
+fn find_untracked() -> Result<Vec<PathBuf>, CommandError> {
+    let git_storage_folder_path = match utils::get_git_storage_folder_path() {
+        Ok(path) => path,
+        Err(err) => {
+            return Err(ExecutionError::GetStorageFolderPath(err.to_string()).into());
+        }
+    };
+
+    let home_folder_path = utils::get_home_folder_path()?;
+    let config_folder_path = get_config_folder_path()?;
+
+    let candidates = HOME_CANDIDATES
+        .iter()
+        .map(|name| home_folder_path.join(name))
+        .chain(
+            CONFIG_CANDIDATES
+                .iter()
+                .map(|name| config_folder_path.join(name)),
+        );
+
+    let mut untracked = Vec::new();
+    let mut seen = BTreeSet::new();
+
+    for path in candidates {
+        if !path.is_file() && !path.is_dir() {
+            continue;
+        }
+
+        let name = paths::get_name(&path, true)?;
+
+        // A folder that is partially tracked is left alone since adding it
+        // again would fail
+        if utils::check_if_file_exists(&git_storage_folder_path, &name) || !seen.insert(name) {
+            continue;
+        }
+
+        untracked.push(path);
+    }
+
+    Ok(untracked)
+}
+
+/// Parses the numbers picked by the user, returning the indexes of the files
+fn parse_selection(answer: &str, count: usize) -> Result<Vec<usize>, Error> {
+    if answer == "all" {
+        return Ok((0..count).collect());
+    }
+
+    let mut indexes = BTreeSet::new();
+
+    for part in answer.split(|c: char| c.is_whitespace() || c == ',') {
+        if part.is_empty() {
+            continue;
+        }
+
+        match part.parse::<usize>() {
+            Ok(number) if (1..=count).contains(&number) => {
+                indexes.insert(number - 1);
+            }
+            _ => return Err(Error::InvalidSelection(part.to_string())),
+        }
+    }
+
+    Ok(indexes.into_iter().collect())
+}
+
+impl Command for Adopt {
+    fn execute(self) -> Result<String, CommandError> {
+        let untracked = find_untracked()?;
+
+        if untracked.is_empty() {
+            return Err(Error::NothingToAdopt.into());
+        }
+
+        let selected = if self.all {
+            untracked
+        } else {
+            for (index, path) in untracked.iter().enumerate() {
+                println!("{}. {}", index + 1, path.display().to_string().cyan());
+            }
+
+            let answer = utils::prompt(
+                "Pick the files to add by their numbers, separated by spaces, or type all (empty to cancel):",
+            )?
+            .unwrap_or_default();
+
+            parse_selection(&answer, untracked.len())?
+                .into_iter()
+                .map(|index| untracked[index].clone())
+                .collect()
+        };
+
+        if selected.is_empty() {
+            return Ok("Nothing was adopted".to_string());
+        }
+
+        // Files in a config folder outside of home are tracked like the ones
+        // added with --force
+        Add::new(selected, true, self.message).execute()
+    }
+}
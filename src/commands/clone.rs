@@ -1,14 +1,22 @@
-use super::Command;
+use super::{completions, Command};
+use crate::deploy;
 use crate::error::{CommandError, ExecutionError};
+use crate::hooks::{self, Context, Event, Stage};
+use crate::manifest::Manifest;
+use crate::paths;
+use crate::permissions;
+use crate::state::{self, Change, State};
 use crate::utils;
 use clap::Args;
-use std::{env, fs};
+use clap_complete::ArgValueCandidates;
+use std::env;
+use std::path::PathBuf;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum Error {
-    #[error("File does not exist in the repository")]
-    FileDoesNotExists,
+    #[error("{0} changed since it was deployed, run dfmn update to keep your changes first")]
+    ChangedLocally(String),
 }
 
 impl From<Error> for CommandError {
@@ -17,16 +25,21 @@ impl From<Error> for CommandError {
     }
 }
 
-/// Clones a file from the repository to your current directory
+/// Clones files from the repository to your current directory
 #[derive(Debug, Args)]
 pub struct Clone {
-    /// File name
-    name: String,
+    /// File names or paths, accepts glob patterns over the names relative to
+    /// your home folder, like '.config/nvim/**'
+    #[arg(add = ArgValueCandidates::new(completions::tracked_names), required = true)]
+    names: Vec<String>,
+    /// Does not run the hooks
+    #[arg(long)]
+    no_hooks: bool,
 }
 
 impl Command for Clone {
     fn execute(self) -> Result<String, CommandError> {
-        let git_storage_folder_path = match utils::get_dfm_folder_path() {
+        let git_storage_folder_path = match utils::get_git_storage_folder_path() {
             Ok(path) => path,
             Err(err) => {
                 return Err(ExecutionError::GetStorageFolderPath(err.to_string()).into());
@@ -40,9 +53,12 @@ impl Command for Clone {
             }
         };
 
-        if !utils::check_if_file_exists(&git_storage_folder_path, &self.name) {
-            return Err(Error::FileDoesNotExists.into());
-        }
+        let manifest = Manifest::load(&git_storage_folder_path)?;
+        let names: Vec<String> =
+            paths::resolve_tracked(&git_storage_folder_path, &manifest, &self.names)?
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect();
 
         let current_dir = match env::current_dir() {
             Ok(path) => path,
@@ -51,13 +67,75 @@ impl Command for Clone {
             }
         };
 
-        if let Err(err) = fs::copy(
-            git_storage_folder_path.join(&self.name),
-            current_dir.join(&self.name),
-        ) {
-            return Err(ExecutionError::CopyFile(err.to_string()).into());
+        let files: Vec<(&String, PathBuf)> = names
+            .iter()
+            .map(|name| (name, current_dir.join(name)))
+            .collect();
+
+        // Cloning inside the home folder can overwrite the live files, which is
+        // refused when they have changes that are not in the repository
+        let mut state = State::load()?;
+        let mut live_files = Vec::new();
+
+        for (name, destination) in &files {
+            if manifest.destination(name)? != *destination {
+                continue;
+            }
+
+            match state::classify(
+                &git_storage_folder_path,
+                state.hash(name),
+                name,
+                destination,
+            )? {
+                Change::Local | Change::Both => {
+                    return Err(Error::ChangedLocally(name.to_string()).into());
+                }
+                _ => live_files.push(*name),
+            }
+        }
+
+        let context = match files.as_slice() {
+            [(name, destination)] => Context {
+                file: Some(name),
+                destination: Some(destination),
+            },
+            _ => Context::default(),
+        };
+
+        if !self.no_hooks {
+            hooks::run(&manifest.hooks, Stage::Pre, Event::Deploy, &context)?;
+        }
+
+        for (name, destination) in &files {
+            if !self.no_hooks {
+                hooks::run_for_file(&manifest, name, destination, Stage::Pre, Event::Deploy)?;
+            }
+
+            let entry = manifest.files.get(*name);
+
+            deploy::deploy_file(
+                &git_storage_folder_path.join(name),
+                destination,
+                permissions::get_recorded_mode(entry),
+            )?;
+            permissions::restore(entry, destination)?;
+
+            if live_files.contains(name) {
+                state.record(&git_storage_folder_path, name, destination)?;
+            }
+
+            if !self.no_hooks {
+                hooks::run_for_file(&manifest, name, destination, Stage::Post, Event::Deploy)?;
+            }
+        }
+
+        state.save()?;
+
+        if !self.no_hooks {
+            hooks::run(&manifest.hooks, Stage::Post, Event::Deploy, &context)?;
         }
 
-        Ok("Successfully cloned the file to your current directory".to_string())
+        Ok("Successfully cloned the file(s) to your current directory".to_string())
     }
 }
@@ -0,0 +1,66 @@
+use super::Command;
+use crate::error::{CommandError, ExecutionError};
+use crate::utils;
+use clap::Args;
+use clap_complete::env::Shells;
+use clap_complete::{CompletionCandidate, Shell};
+use std::io;
+use thiserror::Error;
+
+/// Env var that makes dfmn answer the completion requests of the shells
+pub const COMPLETE_VAR: &str = "COMPLETE";
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Completions are not supported for {0}")]
+    UnsupportedShell(Shell),
+}
+
+impl From<Error> for CommandError {
+    fn from(err: Error) -> Self {
+        CommandError::Usage(err.to_string())
+    }
+}
+
+/// Prints the script that enables the completions of dfmn in your shell
+#[derive(Debug, Args)]
+pub struct Completions {
+    /// Shell to print the script for
+    shell: Shell,
+}
+
+/// Completes the names of the tracked files, reading them from the repository
+pub(super) fn tracked_names() -> Vec<CompletionCandidate> {
+    let Ok(git_storage_folder_path) = utils::get_git_storage_folder_path() else {
+        return Vec::new();
+    };
+
+    utils::get_tracked_files(&git_storage_folder_path)
+        .unwrap_or_default()
+        .into_iter()
+        .map(CompletionCandidate::new)
+        .collect()
+}
+
+impl Command for Completions {
+    fn execute(self) -> Result<String, CommandError> {
+        let shells = Shells::builtins();
+
+        let Some(completer) = shells.completer(&self.shell.to_string()) else {
+            return Err(Error::UnsupportedShell(self.shell).into());
+        };
+
+        if let Err(err) =
+            completer.write_registration(COMPLETE_VAR, "dfmn", "dfmn", "dfmn", &mut io::stdout())
+        {
+            return Err(ExecutionError::Unknown {
+                err: err.to_string(),
+                trying_to: "write the completion script",
+            }
+            .into());
+        }
+
+        // Nothing else is printed so the output can be sourced by the shell
+        Ok(String::new())
+    }
+}
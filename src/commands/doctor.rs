@@ -0,0 +1,291 @@
+use super::Command;
+use crate::error::{CommandError, ExecutionError};
+use crate::git;
+use crate::manifest::Manifest;
+use crate::utils;
+use clap::Args;
+use colored::Colorize;
+use std::env;
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Found {0} problem(s)")]
+    ProblemsFound(usize),
+}
+
+impl From<Error> for CommandError {
+    fn from(err: Error) -> Self {
+        CommandError::Usage(err.to_string())
+    }
+}
+
+/// Checks the health of your dfmn and explains how to fix the problems found
+#[derive(Debug, Args)]
+pub struct Doctor {
+    /// Skips the checks that need to reach the remote repository
+    #[arg(long)]
+    offline: bool,
+}
+
+#[derive(Default)]
+struct Report {
+    problems: usize,
+}
+
+impl Report {
+    fn ok(&self, message: &str) {
+        println!("{} {message}", "ok".green());
+    }
+
+    fn problem(&mut self, message: &str, fix: &str) {
+        println!("{} {message}", "problem".red());
+        println!("    {} {fix}", "fix:".yellow());
+
+        self.problems += 1;
+    }
+}
+
+/// Returns whether git can be used for the rest of the checks
+fn check_git(report: &mut Report) -> bool {
+    match git::get_version() {
+        Ok(version) => {
+            report.ok(&version);
+
+            true
+        }
+        Err(err) => {
+            report.problem(
+                &format!("git is not available: {err}"),
+                "install git and make sure it is in your PATH",
+            );
+
+            false
+        }
+    }
+}
+
+fn check_remote_link(
+    report: &mut Report,
+    storage_folder_path: &Path,
+    git_storage_folder_path: &Path,
+    folder: &str,
+) -> Result<Option<String>, CommandError> {
+    let remote_link = match fs::read_to_string(storage_folder_path.join("remote.txt")) {
+        Ok(link) => Some(link.trim().to_string()),
+        Err(_) => None,
+    };
+
+    let origin = git::get_remote_url(git_storage_folder_path)?;
+
+    match (&remote_link, &origin) {
+        (None, None) => report.problem(
+            "no remote repository is set",
+            "run `dfmn remote add <repository-ssh-link>`",
+        ),
+        (Some(link), None) => report.problem(
+            "the remote repository is set but the origin remote is missing",
+            &format!("run `git -C {folder} remote add origin {link}`"),
+        ),
+        (None, Some(_)) => report.problem(
+            "the origin remote exists but dfmn has no remote repository set",
+            "run `dfmn reset` and then `dfmn remote add <repository-ssh-link>`",
+        ),
+        (Some(link), Some(origin)) if link != origin => report.problem(
+            &format!("the origin remote ({origin}) differs from the remote repository ({link})"),
+            &format!("run `git -C {folder} remote set-url origin {link}`"),
+        ),
+        (Some(link), Some(_)) => report.ok(&format!("remote repository is {link}")),
+    }
+
+    Ok(origin)
+}
+
+fn check_remote_access(
+    report: &mut Report,
+    git_storage_folder_path: &Path,
+) -> Result<(), CommandError> {
+    let branches = match git::list_remote_branches(git_storage_folder_path)? {
+        Ok(branches) => branches,
+        Err(err) => {
+            let fix = if env::var("SSH_AUTH_SOCK").is_err() {
+                "no SSH agent is running, start one with `eval $(ssh-agent)` and load your key with `ssh-add`"
+            } else {
+                "check that your key is loaded with `ssh-add -l` and that it is added to your GitHub or GitLab account"
+            };
+
+            report.problem(
+                &format!("can not access the remote repository: {}", err.trim()),
+                fix,
+            );
+
+            return Ok(());
+        }
+    };
+
+    report.ok("the remote repository is accessible");
+
+    if branches.is_empty() {
+        report.ok("the remote repository is empty");
+    } else if branches.iter().any(|branch| branch == "main") {
+        report.ok("the remote repository has the main branch");
+    } else {
+        report.problem(
+            "the remote repository has no main branch, which is the one used by dfmn",
+            "rename the default branch of the remote repository to main",
+        );
+    }
+
+    Ok(())
+}
+
+fn check_local_repository(
+    report: &mut Report,
+    git_storage_folder_path: &Path,
+    folder: &str,
+) -> Result<(), CommandError> {
+    if git::get_head(git_storage_folder_path)?.is_some() {
+        if git::branch_exists(git_storage_folder_path, "main")? {
+            report.ok("the local repository has the main branch");
+        } else {
+            report.problem(
+                "the local repository has no main branch",
+                &format!("run `git -C {folder} branch -m main`"),
+            );
+        }
+    }
+
+    match git::count_unpushed_commits(git_storage_folder_path)? {
+        Some(0) | None => report.ok("there are no unpushed commits"),
+        Some(count) => report.problem(
+            &format!("there are {count} commit(s) that were not pushed"),
+            &format!("run `git -C {folder} push origin main`"),
+        ),
+    }
+
+    let dirty_files = git::get_dirty_files(git_storage_folder_path)?;
+
+    if dirty_files.is_empty() {
+        report.ok("the working tree is clean");
+    } else {
+        report.problem(
+            &format!(
+                "the working tree has uncommitted changes: {}",
+                dirty_files.join(", ")
+            ),
+            &format!("inspect them with `git -C {folder} status` and commit or discard them"),
+        );
+    }
+
+    Ok(())
+}
+
+fn check_deployed_files(
+    report: &mut Report,
+    git_storage_folder_path: &Path,
+) -> Result<(), CommandError> {
+    let manifest = match Manifest::load(git_storage_folder_path) {
+        Ok(manifest) => manifest,
+        Err(err) => {
+            report.problem(
+                &err.to_string(),
+                "fix the syntax of the dfmn.toml file in the repository",
+            );
+
+            return Ok(());
+        }
+    };
+
+    let mut broken_links = 0;
+
+    for name in utils::get_tracked_files(git_storage_folder_path)? {
+        let destination = manifest.destination(&name)?;
+
+        let Ok(metadata) = fs::symlink_metadata(&destination) else {
+            continue;
+        };
+
+        if metadata.file_type().is_symlink() && !destination.exists() {
+            report.problem(
+                &format!("{} is a broken symbolic link", destination.display()),
+                &format!(
+                    "remove it with `rm {}` and deploy {name} again",
+                    destination.display()
+                ),
+            );
+
+            broken_links += 1;
+        }
+    }
+
+    if broken_links == 0 {
+        report.ok("there are no broken deployed links");
+    }
+
+    Ok(())
+}
+
+impl Command for Doctor {
+    fn execute(self) -> Result<String, CommandError> {
+        let storage_folder_path = match utils::get_dfm_folder_path() {
+            Ok(path) => path,
+            Err(err) => {
+                return Err(ExecutionError::GetStorageFolderPath(err.to_string()).into());
+            }
+        };
+
+        let git_storage_folder_path = storage_folder_path.join("dotfiles");
+
+        let Some(folder) = git_storage_folder_path.to_str() else {
+            return Err(ExecutionError::InvalidUTF8("convert Path to &str").into());
+        };
+
+        let mut report = Report::default();
+
+        if !check_git(&mut report) {
+            return Err(Error::ProblemsFound(report.problems).into());
+        }
+
+        if !git_storage_folder_path.is_dir() {
+            report.problem(
+                &format!("{folder} does not exist"),
+                "run `dfmn init <repository-ssh-link>` to set up this machine",
+            );
+
+            return Err(Error::ProblemsFound(report.problems).into());
+        }
+
+        if git::is_repository_root(&git_storage_folder_path)? {
+            report.ok(&format!("{folder} is a git repository"));
+        } else {
+            report.problem(
+                &format!("{folder} is not a git repository"),
+                &format!("remove {folder} and run dfmn again to recreate it"),
+            );
+
+            return Err(Error::ProblemsFound(report.problems).into());
+        }
+
+        let origin = check_remote_link(
+            &mut report,
+            &storage_folder_path,
+            &git_storage_folder_path,
+            folder,
+        )?;
+
+        if origin.is_some() && !self.offline {
+            check_remote_access(&mut report, &git_storage_folder_path)?;
+        }
+
+        check_local_repository(&mut report, &git_storage_folder_path, folder)?;
+        check_deployed_files(&mut report, &git_storage_folder_path)?;
+
+        if report.problems > 0 {
+            return Err(Error::ProblemsFound(report.problems).into());
+        }
+
+        Ok("No problems found".to_string())
+    }
+}
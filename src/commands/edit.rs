@@ -0,0 +1,191 @@
+use super::{completions, Command};
+use crate::deploy::{self, Outcome};
+use crate::error::{CommandError, ExecutionError};
+use crate::git::GitCommandExecuterBuilder;
+use crate::hooks::{self, Context, Event, Stage};
+use crate::manifest::Manifest;
+use crate::message::MessageArgs;
+use crate::paths;
+use crate::permissions;
+use crate::state;
+use crate::utils;
+use clap::Args;
+use clap_complete::ArgValueCandidates;
+use colored::Colorize;
+use std::fs;
+use std::path::Path;
+use std::slice;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("You can just edit one file at a time")]
+    MoreThanOneFile,
+    #[error("The file was not changed")]
+    NothingChanged,
+    #[error("You need to set a remote repository before use dfmn")]
+    SetRemoteRepository,
+}
+
+impl From<Error> for CommandError {
+    fn from(err: Error) -> Self {
+        CommandError::Usage(err.to_string())
+    }
+}
+
+/// Opens a tracked file in your editor and commits the changes when you close it
+#[derive(Debug, Args)]
+pub struct Edit {
+    /// File name or path
+    #[arg(add = ArgValueCandidates::new(completions::tracked_names))]
+    name: String,
+    /// Edits the repository copy and deploys it afterwards, instead of the live file
+    #[arg(short, long)]
+    repository: bool,
+    #[command(flatten)]
+    message: MessageArgs,
+    /// Does not run the hooks
+    #[arg(long)]
+    no_hooks: bool,
+}
+
+fn read_file(path: &Path) -> Result<Vec<u8>, ExecutionError> {
+    match fs::read(path) {
+        Ok(content) => Ok(content),
+        Err(err) => Err(ExecutionError::ReadFile(err.to_string())),
+    }
+}
+
+impl Command for Edit {
+    fn execute(self) -> Result<String, CommandError> {
+        let git_storage_folder_path = match utils::get_git_storage_folder_path() {
+            Ok(path) => path,
+            Err(err) => {
+                return Err(ExecutionError::GetStorageFolderPath(err.to_string()).into());
+            }
+        };
+
+        let git_storage_folder_path = match git_storage_folder_path.canonicalize() {
+            Ok(path) => path,
+            Err(err) => {
+                return Err(ExecutionError::CanonicalizePath(err.to_string()).into());
+            }
+        };
+
+        if utils::check_if_remote_link_is_added().is_err() {
+            return Err(Error::SetRemoteRepository.into());
+        }
+
+        let mut manifest = Manifest::load(&git_storage_folder_path)?;
+
+        let (name, live_file_path) =
+            match paths::resolve_tracked(&git_storage_folder_path, &manifest, &[self.name])?
+                .as_slice()
+            {
+                [file] => file.clone(),
+                _ => return Err(Error::MoreThanOneFile.into()),
+            };
+
+        let stored_file_path = git_storage_folder_path.join(&name);
+
+        // A file that was not deployed on this machine can only be edited in the repository
+        let edit_repository = self.repository || !live_file_path.is_file();
+
+        let edited_file_path = if edit_repository {
+            &stored_file_path
+        } else {
+            &live_file_path
+        };
+
+        let previous_content = read_file(&stored_file_path)?;
+
+        utils::open_editor(edited_file_path)?;
+
+        if read_file(edited_file_path)? == previous_content {
+            return Err(Error::NothingChanged.into());
+        }
+
+        let message = self.message.build("Edit", slice::from_ref(&name))?;
+
+        let context = Context {
+            file: Some(&name),
+            destination: Some(&live_file_path),
+        };
+
+        if !self.no_hooks {
+            hooks::run(&manifest.hooks, Stage::Pre, Event::Update, &context)?;
+            hooks::run_for_file(&manifest, &name, &live_file_path, Stage::Pre, Event::Update)?;
+        }
+
+        if !edit_repository {
+            if let Err(err) = fs::copy(&live_file_path, &stored_file_path) {
+                return Err(ExecutionError::CopyFile(err.to_string()).into());
+            }
+
+            permissions::record(
+                manifest.files.entry(name.clone()).or_default(),
+                &live_file_path,
+                false,
+            )?;
+
+            manifest.save(&git_storage_folder_path)?;
+        }
+
+        let is_online = online::check(None).is_ok();
+        let mut git_command =
+            GitCommandExecuterBuilder::new(&git_storage_folder_path).run_commit(message);
+
+        if !is_online {
+            git_command = git_command.offline();
+        }
+
+        git_command.build().run()?;
+
+        if !edit_repository {
+            state::record(&git_storage_folder_path, &name, &live_file_path)?;
+        }
+
+        if !self.no_hooks {
+            hooks::run_for_file(
+                &manifest,
+                &name,
+                &live_file_path,
+                Stage::Post,
+                Event::Update,
+            )?;
+            hooks::run(&manifest.hooks, Stage::Post, Event::Update, &context)?;
+        }
+
+        if edit_repository {
+            // The live file is only overwritten without asking when it had the
+            // version that was just edited
+            let force = !live_file_path.exists() || read_file(&live_file_path)? == previous_content;
+
+            match deploy::deploy_tracked_file(
+                &git_storage_folder_path,
+                &manifest,
+                &name,
+                force,
+                self.no_hooks,
+            )? {
+                Outcome::Deployed | Outcome::BackedUp(_) => {
+                    println!("{} {}", "deployed".green(), live_file_path.display());
+                }
+                Outcome::UpToDate => {}
+                Outcome::Skipped => {
+                    println!("{} {}", "skipped".yellow(), live_file_path.display());
+                }
+            }
+        }
+
+        if !is_online {
+            return Ok(format!(
+                "Committed the changes of {name}, they will be pushed the next time dfmn reaches the remote repository"
+            ));
+        }
+
+        Ok(format!(
+            "Successfully edited {name} and synchronized the local repository with the remote repository"
+        ))
+    }
+}
@@ -0,0 +1,186 @@
+use super::Command;
+use crate::dfmnignore::IgnoreMatcher;
+use crate::error::{CommandError, ExecutionError};
+use crate::git::{self, GitCommandExecuterBuilder};
+use crate::manifest::Manifest;
+use crate::utils;
+use clap::Args;
+use colored::Colorize;
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("You need to set a remote repository before use dfmn")]
+    SetRemoteRepository,
+    #[error("No internet connection")]
+    NoInternetConnection,
+}
+
+impl From<Error> for CommandError {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::NoInternetConnection => CommandError::Network(err.to_string()),
+            err => CommandError::Usage(err.to_string()),
+        }
+    }
+}
+
+/// Downloads the changes of the remote repository and lists the files they
+/// change, without applying them
+#[derive(Debug, Args)]
+pub struct Fetch;
+
+/// How a file changes when the commits of the remote repository are merged
+pub(super) enum Change {
+    Added,
+    Modified,
+    Deleted,
+}
+
+pub(super) struct Incoming {
+    pub name: String,
+    pub change: Change,
+}
+
+/// Fetches the remote repository and returns the files that its new commits
+/// change, leaving out the ignored ones
+pub(super) fn fetch_incoming(
+    git_storage_folder_path: &Path,
+) -> Result<Vec<Incoming>, CommandError> {
+    GitCommandExecuterBuilder::new(git_storage_folder_path)
+        .run_fetch()
+        .build()
+        .run()?;
+
+    let ignore_matcher = IgnoreMatcher::load(git_storage_folder_path)?;
+    let mut incoming = Vec::new();
+
+    for (status, name) in git::get_incoming_changes(git_storage_folder_path)? {
+        if ignore_matcher.is_ignored(&name, false) {
+            continue;
+        }
+
+        let change = match status {
+            'A' => Change::Added,
+            'D' => Change::Deleted,
+            _ => Change::Modified,
+        };
+
+        incoming.push(Incoming { name, change });
+    }
+
+    Ok(incoming)
+}
+
+pub(super) fn print_incoming(incoming: &Incoming) {
+    let label = match incoming.change {
+        Change::Added => "added".green(),
+        Change::Modified => "modified".yellow(),
+        Change::Deleted => "deleted".red(),
+    };
+
+    println!("{label} {}", incoming.name);
+}
+
+/// Prints how the live file would change with the version of the remote repository
+pub(super) fn print_incoming_diff(
+    git_storage_folder_path: &Path,
+    manifest: &Manifest,
+    incoming: &Incoming,
+) -> Result<(), CommandError> {
+    let destination = manifest.destination(&incoming.name)?;
+
+    let Some(content) = git::get_file_at(git_storage_folder_path, "origin/main", &incoming.name)?
+    else {
+        return Ok(());
+    };
+
+    if !destination.is_file() {
+        println!("  {}", "not deployed on this machine".dimmed());
+
+        return Ok(());
+    }
+
+    // git diff needs both versions in files, so the incoming one is written
+    // next to the storage folder
+    let incoming_file_path = utils::get_dfm_folder_path()?.join("INCOMING");
+
+    if let Err(err) = fs::write(&incoming_file_path, content) {
+        return Err(ExecutionError::WriteToFile(err.to_string()).into());
+    }
+
+    let diff = git::diff_files(git_storage_folder_path, &destination, &incoming_file_path);
+
+    if let Err(err) = fs::remove_file(&incoming_file_path) {
+        return Err(ExecutionError::RemoveFile(err.to_string()).into());
+    }
+
+    let diff = diff?;
+
+    if diff.is_empty() {
+        println!("  {}", "the live file already has these changes".dimmed());
+
+        return Ok(());
+    }
+
+    // The header of git names the temporary file, so it is replaced
+    println!("{}", format!("--- {}", destination.display()).bold());
+    println!("{}", format!("+++ origin/main:{}", incoming.name).bold());
+
+    for line in diff.lines().skip_while(|line| !line.starts_with("@@")) {
+        if line.starts_with("@@") {
+            println!("{}", line.cyan());
+        } else if line.starts_with('+') {
+            println!("{}", line.green());
+        } else if line.starts_with('-') {
+            println!("{}", line.red());
+        } else {
+            println!("{line}");
+        }
+    }
+
+    Ok(())
+}
+
+impl Command for Fetch {
+    fn execute(self) -> Result<String, CommandError> {
+        if online::check(None).is_err() {
+            return Err(Error::NoInternetConnection.into());
+        }
+
+        let git_storage_folder_path = match utils::get_git_storage_folder_path() {
+            Ok(path) => path,
+            Err(err) => {
+                return Err(ExecutionError::GetStorageFolderPath(err.to_string()).into());
+            }
+        };
+
+        let git_storage_folder_path = match git_storage_folder_path.canonicalize() {
+            Ok(path) => path,
+            Err(err) => {
+                return Err(ExecutionError::CanonicalizePath(err.to_string()).into());
+            }
+        };
+
+        if utils::check_if_remote_link_is_added().is_err() {
+            return Err(Error::SetRemoteRepository.into());
+        }
+
+        let incoming = fetch_incoming(&git_storage_folder_path)?;
+
+        if incoming.is_empty() {
+            return Ok("Already up to date with the remote repository".to_string());
+        }
+
+        for incoming in &incoming {
+            print_incoming(incoming);
+        }
+
+        Ok(format!(
+            "{} file(s) will change, run dfmn sync --preview to see how or dfmn sync to apply them",
+            incoming.len()
+        ))
+    }
+}
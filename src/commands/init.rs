@@ -0,0 +1,123 @@
+use super::{remote, Command};
+use crate::deploy::{self, Outcome};
+use crate::error::{CommandError, ExecutionError};
+use crate::hooks::{self, Context, Event, Stage};
+use crate::manifest::Manifest;
+use crate::utils;
+use clap::Args;
+use colored::Colorize;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("No internet connection")]
+    NoInternetConnection,
+}
+
+impl From<Error> for CommandError {
+    fn from(err: Error) -> Self {
+        CommandError::Network(err.to_string())
+    }
+}
+
+/// Sets the remote repository and deploys all of its files to your home folder
+#[derive(Debug, Args)]
+pub struct Init {
+    /// Remote repository link
+    link: String,
+    /// Overwrites the files that already exist without asking
+    #[arg(short, long)]
+    force: bool,
+    /// Does not run the hooks
+    #[arg(long)]
+    no_hooks: bool,
+}
+
+impl Command for Init {
+    fn execute(self) -> Result<String, CommandError> {
+        if online::check(None).is_err() {
+            return Err(Error::NoInternetConnection.into());
+        }
+
+        let storage_folder_path = match utils::get_dfm_folder_path() {
+            Ok(path) => path,
+            Err(err) => {
+                return Err(ExecutionError::GetStorageFolderPath(err.to_string()).into());
+            }
+        };
+
+        let storage_folder_path = match storage_folder_path.canonicalize() {
+            Ok(path) => path,
+            Err(err) => {
+                return Err(ExecutionError::CanonicalizePath(err.to_string()).into());
+            }
+        };
+
+        let git_storage_folder_path = storage_folder_path.join("dotfiles");
+
+        remote::add_remote_link(&storage_folder_path, &git_storage_folder_path, &self.link)?;
+
+        let manifest = Manifest::load(&git_storage_folder_path)?;
+
+        let mut deployed = 0;
+        let mut skipped = 0;
+
+        if !self.no_hooks {
+            hooks::run(
+                &manifest.hooks,
+                Stage::Pre,
+                Event::Deploy,
+                &Context::default(),
+            )?;
+        }
+
+        for name in utils::get_tracked_files(&git_storage_folder_path)? {
+            let destination = manifest.destination(&name)?;
+
+            match deploy::deploy_tracked_file(
+                &git_storage_folder_path,
+                &manifest,
+                &name,
+                self.force,
+                self.no_hooks,
+            )? {
+                Outcome::Deployed => {
+                    println!("{} {}", "deployed".green(), destination.display());
+
+                    deployed += 1;
+                }
+                Outcome::BackedUp(backup_path) => {
+                    println!(
+                        "{} {} (backup at {})",
+                        "deployed".green(),
+                        destination.display(),
+                        backup_path.display()
+                    );
+
+                    deployed += 1;
+                }
+                Outcome::UpToDate => {
+                    println!("{} {}", "up to date".cyan(), destination.display());
+                }
+                Outcome::Skipped => {
+                    println!("{} {}", "skipped".yellow(), destination.display());
+
+                    skipped += 1;
+                }
+            }
+        }
+
+        if !self.no_hooks && deployed > 0 {
+            hooks::run(
+                &manifest.hooks,
+                Stage::Post,
+                Event::Deploy,
+                &Context::default(),
+            )?;
+        }
+
+        Ok(format!(
+            "Successfully setted the remote repository and deployed {deployed} file(s), {skipped} skipped"
+        ))
+    }
+}
@@ -1,30 +1,297 @@
-use super::Command;
+use super::{completions, Command};
 use crate::error::{CommandError, ExecutionError};
+use crate::git::{self, LastCommit};
+use crate::manifest::Manifest;
+use crate::state::{self, Change, State};
 use crate::utils;
-use clap::Args;
-use colored::Colorize;
+use clap::{Args, ValueEnum};
+use clap_complete::ArgValueCandidates;
+use colored::{ColoredString, Colorize};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::{self, IsTerminal};
 use std::path::Path;
-use thiserror::Error;
-use walkdir::WalkDir;
 
-#[derive(Debug, Error)]
-pub enum Error {
-    #[error("Your remote repository is empty")]
-    EmptyRepository,
+/// Lists the files that are in the remote repository
+#[derive(Debug, Args)]
+pub struct List {
+    /// Lists only the files that match these glob patterns or are inside these folders
+    #[arg(add = ArgValueCandidates::new(completions::tracked_names))]
+    patterns: Vec<String>,
+    /// Shows the files as a tree of their folders
+    #[arg(short, long)]
+    tree: bool,
+    /// Columns shown next to every file, separated by commas
+    #[arg(short, long, value_enum, value_delimiter = ',')]
+    columns: Vec<Column>,
+    /// Shows every column
+    #[arg(short, long, conflicts_with = "columns")]
+    long: bool,
+    /// Order of the files
+    #[arg(short, long, value_enum, default_value_t = Sort::Name)]
+    sort: Sort,
+    /// Reverses the order of the files
+    #[arg(short, long)]
+    reverse: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Column {
+    /// Whether the live file changed, see the README for the markers
+    Status,
+    /// Size of the repository version
+    Size,
+    /// Date of the last commit that changed the file
+    Date,
+    /// Message of the last commit that changed the file
+    Message,
+    /// Where the file is deployed on this machine
+    Destination,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Sort {
+    Name,
+    Size,
+    Date,
+}
+
+/// Everything that can be shown about a tracked file
+struct Entry {
+    name: String,
+    size: u64,
+    last_commit: Option<LastCommit>,
+    destination: String,
+    status: ColoredString,
 }
 
-impl From<Error> for CommandError {
-    fn from(err: Error) -> Self {
-        CommandError::Usage(err.to_string())
+/// A folder of the tree, with the position of the first of its files in the
+/// sorted list so the folders follow the order of their files
+#[derive(Default)]
+struct Folder {
+    rank: usize,
+    folders: BTreeMap<String, Folder>,
+    files: Vec<(String, usize)>,
+}
+
+impl Folder {
+    fn insert(&mut self, components: &[&str], index: usize) {
+        match components {
+            [] => {}
+            [file] => self.files.push((file.to_string(), index)),
+            [folder, rest @ ..] => {
+                let folder = self
+                    .folders
+                    .entry(folder.to_string())
+                    .or_insert_with(|| Folder {
+                        rank: index,
+                        ..Folder::default()
+                    });
+
+                folder.insert(rest, index);
+            }
+        }
+    }
+
+    /// Returns the rows of the tree as their label and the index of the file,
+    /// which is `None` for the folders
+    fn rows(&self, prefix: &str, rows: &mut Vec<(String, Option<usize>)>) {
+        let mut children: Vec<(usize, &str, Option<&Folder>)> = self
+            .folders
+            .iter()
+            .map(|(name, folder)| (folder.rank, name.as_str(), Some(folder)))
+            .chain(
+                self.files
+                    .iter()
+                    .map(|(name, index)| (*index, name.as_str(), None)),
+            )
+            .collect();
+
+        children.sort_by_key(|(rank, _, _)| *rank);
+
+        for (position, (rank, name, folder)) in children.iter().enumerate() {
+            let is_last = position + 1 == children.len();
+            let branch = if is_last { "└── " } else { "├── " };
+
+            match folder {
+                Some(folder) => {
+                    rows.push((format!("{prefix}{branch}{name}/"), None));
+
+                    let nested_prefix = if is_last { "    " } else { "│   " };
+                    folder.rows(&format!("{prefix}{nested_prefix}"), rows);
+                }
+                None => rows.push((format!("{prefix}{branch}{name}"), Some(*rank))),
+            }
+        }
     }
 }
 
-/// Lists all the files that are in the remote repository
-#[derive(Debug, Args)]
-pub struct List;
+fn format_size(size: u64) -> String {
+    const UNITS: [&str; 3] = ["KiB", "MiB", "GiB"];
+
+    if size < 1024 {
+        return format!("{size} B");
+    }
+
+    let mut size = size as f64 / 1024.0;
+
+    for unit in &UNITS[..UNITS.len() - 1] {
+        if size < 1024.0 {
+            return format!("{size:.1} {unit}");
+        }
+
+        size /= 1024.0;
+    }
+
+    format!("{size:.1} {}", UNITS[UNITS.len() - 1])
+}
+
+/// Returns the marker of the status of a file, telling where it changed since
+/// it was last deployed or committed
+fn get_status(
+    git_storage_folder_path: &Path,
+    state: &State,
+    name: &str,
+    destination: &Path,
+) -> Result<ColoredString, ExecutionError> {
+    Ok(
+        match state::classify(git_storage_folder_path, state.hash(name), name, destination)? {
+            Change::InSync => " ".normal(),
+            Change::Local => "M".yellow(),
+            Change::Remote => "R".cyan(),
+            Change::Both => "C".red(),
+            Change::Unknown => "?".yellow(),
+            Change::Missing => "!".red(),
+        },
+    )
+}
+
+/// Shows the destinations inside the home folder with `~`
+fn format_destination(destination: &Path, home_folder_path: &Path) -> String {
+    match destination.strip_prefix(home_folder_path) {
+        Ok(relative_path) => format!("~/{}", relative_path.display()),
+        Err(_) => destination.display().to_string(),
+    }
+}
+
+impl List {
+    fn get_entries(&self, git_storage_folder_path: &Path) -> Result<Vec<Entry>, CommandError> {
+        let names = utils::filter_tracked_files(
+            utils::get_tracked_files(git_storage_folder_path)?,
+            &self.patterns,
+        )?;
+
+        let needs_commits = self.sort == Sort::Date
+            || self.columns.contains(&Column::Date)
+            || self.columns.contains(&Column::Message);
+
+        let mut last_commits = if needs_commits {
+            git::get_last_commits(git_storage_folder_path)?
+        } else {
+            HashMap::new()
+        };
+
+        let manifest = Manifest::load(git_storage_folder_path)?;
+        let state = State::load()?;
+        let home_folder_path = utils::get_home_folder_path()?;
+        let mut entries = Vec::new();
+
+        for name in names {
+            let size = match fs::metadata(git_storage_folder_path.join(&name)) {
+                Ok(metadata) => metadata.len(),
+                Err(err) => {
+                    return Err(ExecutionError::GetMetadata(err.to_string()).into());
+                }
+            };
+
+            let destination = manifest.destination(&name)?;
+
+            // The status needs to hash the files, so it is only checked when shown
+            let status = if self.columns.contains(&Column::Status) {
+                get_status(git_storage_folder_path, &state, &name, &destination)?
+            } else {
+                " ".normal()
+            };
+
+            entries.push(Entry {
+                last_commit: last_commits.remove(&name),
+                destination: format_destination(&destination, &home_folder_path),
+                name,
+                size,
+                status,
+            });
+        }
+
+        match self.sort {
+            Sort::Name => {}
+            Sort::Size => entries.sort_by_key(|entry| entry.size),
+            // The newest files come first, like in git log
+            Sort::Date => entries.sort_by_key(|entry| {
+                Reverse(entry.last_commit.as_ref().map(|commit| commit.timestamp))
+            }),
+        }
+
+        if self.reverse {
+            entries.reverse();
+        }
+
+        Ok(entries)
+    }
+
+    fn print_row(&self, label: &str, width: usize, entry: Option<&Entry>) {
+        let Some(entry) = entry else {
+            let indent = if self.columns.contains(&Column::Status) {
+                "  "
+            } else {
+                ""
+            };
+
+            println!("{indent}{}", label.blue().bold());
+
+            return;
+        };
+
+        if self.columns.is_empty() {
+            println!("{}", label.cyan());
+
+            return;
+        }
+
+        let mut row = String::new();
+
+        if self.columns.contains(&Column::Status) {
+            row.push_str(&format!("{} ", entry.status));
+        }
+
+        let padding = width - label.chars().count();
+        row.push_str(&format!("{}{}", label.cyan(), " ".repeat(padding)));
+
+        for column in &self.columns {
+            let value = match column {
+                Column::Status => continue,
+                Column::Size => format!("{:>9}", format_size(entry.size)),
+                Column::Date => entry
+                    .last_commit
+                    .as_ref()
+                    .map_or_else(|| format!("{:10}", "-"), |commit| commit.date.clone()),
+                Column::Message => entry
+                    .last_commit
+                    .as_ref()
+                    .map_or_else(|| "-".to_string(), |commit| commit.subject.clone()),
+                Column::Destination => entry.destination.clone(),
+            };
+
+            row.push_str("  ");
+            row.push_str(&value);
+        }
+
+        println!("{}", row.trim_end());
+    }
+}
 
 impl Command for List {
-    fn execute(self) -> Result<String, CommandError> {
+    fn execute(mut self) -> Result<String, CommandError> {
         let git_storage_folder_path = match utils::get_git_storage_folder_path() {
             Ok(path) => path,
             Err(err) => {
@@ -39,36 +306,121 @@ impl Command for List {
             }
         };
 
-        let mut index = 1;
+        if self.long {
+            self.columns = Column::value_variants().to_vec();
+        }
 
-        for entry in WalkDir::new(git_storage_folder_path).max_depth(1) {
-            let entry = match entry {
-                Ok(entry) => entry,
-                Err(err) => {
-                    return Err(ExecutionError::GetDirEntry(err.to_string()).into());
+        let entries = self.get_entries(&git_storage_folder_path)?;
+
+        // Nothing is printed to the pipes, so scripts just get no lines
+        if entries.is_empty() {
+            if io::stdout().is_terminal() {
+                if self.patterns.is_empty() {
+                    return Ok("Your remote repository is empty".to_string());
                 }
-            };
 
-            if Path::new(&entry.path()).is_dir() {
-                continue;
+                return Ok("No files match the patterns".to_string());
             }
 
-            let entry = match entry.file_name().to_str() {
-                Some(entry) => entry,
-                None => {
-                    return Err(ExecutionError::InvalidUTF8("convert OsStr to &str").into());
-                }
-            };
+            return Ok(String::new());
+        }
 
-            println!("{index}. {}", entry.cyan());
+        let rows = if self.tree {
+            let mut root = Folder::default();
 
-            index += 1;
+            for (index, entry) in entries.iter().enumerate() {
+                root.insert(&entry.name.split('/').collect::<Vec<_>>(), index);
+            }
+
+            let mut rows = Vec::new();
+            root.rows("", &mut rows);
+            rows
+        } else {
+            entries
+                .iter()
+                .enumerate()
+                .map(|(index, entry)| (entry.name.clone(), Some(index)))
+                .collect()
+        };
+
+        let width = rows
+            .iter()
+            .map(|(label, _)| label.chars().count())
+            .max()
+            .unwrap_or_default();
+
+        for (label, index) in &rows {
+            self.print_row(label, width, index.map(|index| &entries[index]));
         }
 
-        if index == 1 {
-            return Err(Error::EmptyRepository.into());
+        Ok(String::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_size_picks_the_unit() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1024), "1.0 KiB");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MiB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0 GiB");
+        assert_eq!(format_size(2048 * 1024 * 1024 * 1024), "2048.0 GiB");
+    }
+
+    fn rows(names: &[&str]) -> Vec<(String, Option<usize>)> {
+        let mut root = Folder::default();
+
+        for (index, name) in names.iter().enumerate() {
+            root.insert(&name.split('/').collect::<Vec<_>>(), index);
         }
 
-        Ok("Finished listing your files".to_string())
+        let mut rows = Vec::new();
+        root.rows("", &mut rows);
+        rows
+    }
+
+    #[test]
+    fn folder_rows_draw_the_tree() {
+        let rows = rows(&[
+            ".bashrc",
+            ".config/git/config",
+            ".config/nvim/init.lua",
+            ".zshrc",
+        ]);
+
+        assert_eq!(
+            rows,
+            [
+                ("├── .bashrc".to_string(), Some(0)),
+                ("├── .config/".to_string(), None),
+                ("│   ├── git/".to_string(), None),
+                ("│   │   └── config".to_string(), Some(1)),
+                ("│   └── nvim/".to_string(), None),
+                ("│       └── init.lua".to_string(), Some(2)),
+                ("└── .zshrc".to_string(), Some(3)),
+            ]
+        );
+    }
+
+    #[test]
+    fn folder_rows_follow_the_order_of_the_files() {
+        let rows = rows(&[".zshrc", ".config/nvim/init.lua", ".bashrc"]);
+        let labels: Vec<&str> = rows.iter().map(|(label, _)| label.as_str()).collect();
+
+        assert_eq!(
+            labels,
+            [
+                "├── .zshrc",
+                "├── .config/",
+                "│   └── nvim/",
+                "│       └── init.lua",
+                "└── .bashrc"
+            ]
+        );
     }
 }
@@ -0,0 +1,152 @@
+use super::Command;
+use crate::error::{CommandError, ExecutionError};
+use crate::logger::{self, RUN_LEVEL};
+use clap::Args;
+use colored::Colorize;
+use std::fs;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("The log is empty")]
+    EmptyLog,
+    #[error("No errors were logged")]
+    NoErrors,
+}
+
+impl From<Error> for CommandError {
+    fn from(err: Error) -> Self {
+        CommandError::Usage(err.to_string())
+    }
+}
+
+/// Shows the log of the last runs of dfmn
+#[derive(Debug, Args)]
+pub struct Log {
+    /// Shows only the runs that failed and their errors and warnings
+    #[arg(short, long)]
+    errors: bool,
+    /// Number of runs to show, starting from the most recent one
+    #[arg(short = 'n', long, default_value_t = 10)]
+    runs: usize,
+}
+
+struct Record {
+    level: String,
+    lines: Vec<String>,
+}
+
+/// The records written during one execution of dfmn
+#[derive(Default)]
+struct Run {
+    header: Option<String>,
+    records: Vec<Record>,
+}
+
+impl Record {
+    fn is_error(&self) -> bool {
+        self.level == "ERROR"
+    }
+
+    fn print(&self) {
+        for line in &self.lines {
+            match self.level.as_str() {
+                "ERROR" => println!("{}", line.red()),
+                "WARN" => println!("{}", line.yellow()),
+                "DEBUG" | "TRACE" => println!("{}", line.dimmed()),
+                _ => println!("{line}"),
+            }
+        }
+    }
+}
+
+/// Splits the log into runs. The lines that do not follow the format are
+/// taken as errors since the old versions of dfmn only logged errors
+fn parse(content: &str) -> Vec<Run> {
+    let mut runs: Vec<Run> = Vec::new();
+
+    for line in content.lines().filter(|line| !line.trim().is_empty()) {
+        if line.starts_with(char::is_whitespace) {
+            if let Some(record) = runs.last_mut().and_then(|run| run.records.last_mut()) {
+                record.lines.push(line.to_string());
+
+                continue;
+            }
+        }
+
+        let level = line
+            .strip_prefix('[')
+            .and_then(|line| line.split_once("] "))
+            .and_then(|(_, line)| line.split_whitespace().next());
+
+        if level == Some(RUN_LEVEL) {
+            runs.push(Run {
+                header: Some(line.to_string()),
+                records: Vec::new(),
+            });
+
+            continue;
+        }
+
+        if runs.is_empty() {
+            runs.push(Run::default());
+        }
+
+        if let Some(run) = runs.last_mut() {
+            run.records.push(Record {
+                level: level.unwrap_or("ERROR").to_string(),
+                lines: vec![line.to_string()],
+            });
+        }
+    }
+
+    runs
+}
+
+impl Command for Log {
+    fn execute(self) -> Result<String, CommandError> {
+        let mut content = String::new();
+
+        for path in logger::log_file_paths()? {
+            match fs::read_to_string(path) {
+                Ok(file_content) => content.push_str(&file_content),
+                Err(err) => {
+                    return Err(ExecutionError::ReadFile(err.to_string()).into());
+                }
+            }
+        }
+
+        let mut runs = parse(&content);
+
+        if runs.is_empty() {
+            return Err(Error::EmptyLog.into());
+        }
+
+        if self.errors {
+            runs.retain(|run| run.records.iter().any(Record::is_error));
+
+            for run in &mut runs {
+                run.records
+                    .retain(|record| record.is_error() || record.level == "WARN");
+            }
+
+            if runs.is_empty() {
+                return Err(Error::NoErrors.into());
+            }
+        }
+
+        let shown = runs.len().min(self.runs);
+
+        for run in &runs[runs.len() - shown..] {
+            if let Some(header) = &run.header {
+                println!("{}", header.cyan());
+            }
+
+            for record in &run.records {
+                record.print();
+            }
+        }
+
+        Ok(format!("Showed the last {shown} run(s)"))
+    }
+}
@@ -0,0 +1,235 @@
+use super::Command;
+use crate::cli::CLI;
+use crate::error::{
+    CommandError, ExecutionError, EXIT_CONFLICT, EXIT_GIT, EXIT_INTERNAL, EXIT_NETWORK, EXIT_USAGE,
+};
+use clap::{Args, CommandFactory, ValueHint};
+use clap_mangen::Man as ManPage;
+use roff::{bold, roman, Roff};
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Generates the man pages of dfmn and its subcommands
+#[derive(Debug, Args)]
+pub struct Man {
+    /// Folder where dfmn.1 and a page for every subcommand are written,
+    /// instead of printing dfmn.1
+    #[arg(short, long, value_hint = ValueHint::DirPath)]
+    output: Option<PathBuf>,
+}
+
+/// A titled list of terms and their descriptions, shared by the man page and
+/// the long help
+struct Section {
+    title: &'static str,
+    entries: Vec<(String, &'static str)>,
+}
+
+fn entries(entries: &[(&str, &'static str)]) -> Vec<(String, &'static str)> {
+    entries
+        .iter()
+        .map(|(term, description)| (term.to_string(), *description))
+        .collect()
+}
+
+fn sections() -> Vec<Section> {
+    vec![
+        Section {
+            title: "EXAMPLES",
+            entries: entries(&[
+                (
+                    "dfmn init git@github.com:user/dotfiles.git",
+                    "Sets up a new machine from your remote repository, deploying every file to its place",
+                ),
+                ("dfmn add ~/.zshrc ~/.config/nvim", "Tracks a file and every file of a folder"),
+                (
+                    "dfmn update --all -m \"Tweak my shell\"",
+                    "Commits every tracked file that changed with your own message",
+                ),
+                (
+                    "dfmn remove '.config/nvim/*'",
+                    "Stops tracking the files that match a glob pattern",
+                ),
+                ("dfmn sync", "Pulls the changes of the remote repository"),
+                (
+                    "dfmn watch --push-interval 600",
+                    "Commits the changes as you save the files, pushing them every 10 minutes",
+                ),
+                ("dfmn doctor", "Finds the problems of your setup and explains how to fix them"),
+            ]),
+        },
+        Section {
+            title: "FILES",
+            entries: entries(&[
+                (
+                    "~/.config/dfmn/",
+                    "The storage folder of dfmn, which is %APPDATA%\\dfmn on Windows",
+                ),
+                (
+                    "~/.config/dfmn/dotfiles/",
+                    "The local git repository, holding the tracked files under their paths relative to your home folder",
+                ),
+                (
+                    "~/.config/dfmn/dotfiles/dfmn.toml",
+                    "The manifest with the destinations, modes and hooks of the tracked files",
+                ),
+                (
+                    "~/.config/dfmn/dotfiles/.dfmnignore",
+                    "The patterns of the files that are never tracked, in the gitignore syntax",
+                ),
+                (
+                    "~/.config/dfmn/config.toml",
+                    "The settings of this machine, such as the commit message template and the log rotation",
+                ),
+                (
+                    "~/.config/dfmn/state.toml",
+                    "The hashes of the files as they were last deployed or committed on this machine",
+                ),
+                ("~/.config/dfmn/remote.txt", "The link of the remote repository"),
+                (
+                    "~/.config/dfmn/log.txt",
+                    "The log of dfmn, rotated to log.1.txt and the following files",
+                ),
+            ]),
+        },
+        Section {
+            title: "ENVIRONMENT",
+            entries: entries(&[
+                ("HOME, USERPROFILE", "Your home folder, USERPROFILE is used on Windows"),
+                ("APPDATA", "The folder that holds the storage folder on Windows"),
+                ("PAGER", "The pager opened by show"),
+                ("VISUAL, EDITOR", "The editor opened by edit, to write the commit messages and to resolve the conflicts of merge"),
+                (
+                    "HOSTNAME, COMPUTERNAME, USER, USERNAME",
+                    "The host and user names written in the commit messages",
+                ),
+                (
+                    "GIT_SSH_COMMAND",
+                    "The ssh command used by doctor to reach the remote repository",
+                ),
+                ("COMPLETE", "Set by the completion scripts to ask for completions"),
+                (
+                    "DFMN_EVENT, DFMN_FILE, DFMN_DESTINATION",
+                    "Set for the hooks with the event, the file name and its destination",
+                ),
+            ]),
+        },
+        Section {
+            title: "EXIT STATUS",
+            entries: vec![
+                ("0".to_string(), "Success"),
+                (
+                    EXIT_INTERNAL.to_string(),
+                    "Internal error, such as failing to read or write a file",
+                ),
+                (
+                    EXIT_USAGE.to_string(),
+                    "Usage error, such as an invalid argument or a file that is not tracked",
+                ),
+                (EXIT_NETWORK.to_string(), "No internet connection"),
+                (EXIT_GIT.to_string(), "git failed or could not be executed"),
+                (
+                    EXIT_CONFLICT.to_string(),
+                    "The local repository conflicts with the remote repository",
+                ),
+            ],
+        },
+    ]
+}
+
+/// Renders the sections as plain text to be shown after `dfmn --help`
+pub fn long_help() -> String {
+    let mut help = String::new();
+
+    for section in sections() {
+        help.push_str(&format!("{}:\n", section.title));
+
+        for (term, description) in section.entries {
+            help.push_str(&format!("  {term}\n          {description}\n"));
+        }
+
+        help.push('\n');
+    }
+
+    help.trim_end().to_string()
+}
+
+fn render_sections() -> String {
+    let mut roff = Roff::new();
+
+    for section in sections() {
+        roff.control("SH", [section.title]);
+
+        for (term, description) in section.entries {
+            roff.control("TP", [])
+                .text([bold(term)])
+                .text([roman(description)]);
+        }
+    }
+
+    roff.to_roff()
+}
+
+fn source() -> String {
+    format!("dfmn {}", env!("CARGO_PKG_VERSION"))
+}
+
+/// Renders the page of dfmn, with the sections that clap does not know about
+/// before the version and the authors
+fn render_main_page(command: clap::Command, writer: &mut dyn Write) -> io::Result<()> {
+    let mut page = Vec::new();
+
+    ManPage::new(command.about(env!("CARGO_PKG_DESCRIPTION")))
+        .source(source())
+        .render(&mut page)?;
+
+    let page = String::from_utf8_lossy(&page);
+    let (head, tail) = page.split_at(page.find(".SH VERSION").unwrap_or(page.len()));
+
+    writer.write_all(head.as_bytes())?;
+    writer.write_all(render_sections().as_bytes())?;
+    writer.write_all(tail.as_bytes())
+}
+
+fn generate_pages(command: clap::Command, output: &Path) -> io::Result<()> {
+    let mut file = File::create(output.join("dfmn.1"))?;
+
+    render_main_page(command.clone(), &mut file)?;
+
+    for subcommand in command.get_subcommands() {
+        ManPage::new(subcommand.clone())
+            .source(source())
+            .generate_to(output)?;
+    }
+
+    Ok(())
+}
+
+impl Command for Man {
+    fn execute(self) -> Result<String, CommandError> {
+        let mut command = CLI::command().disable_help_subcommand(true);
+
+        // Builds the names of the subcommands pages, like dfmn-add
+        command.build();
+
+        let Some(output) = self.output else {
+            if let Err(err) = render_main_page(command, &mut io::stdout()) {
+                return Err(ExecutionError::WriteToFile(err.to_string()).into());
+            }
+
+            // Nothing else is printed so the output can be piped to man
+            return Ok(String::new());
+        };
+
+        if let Err(err) = fs::create_dir_all(&output) {
+            return Err(ExecutionError::CreateFolder(err.to_string()).into());
+        }
+
+        if let Err(err) = generate_pages(command, &output) {
+            return Err(ExecutionError::WriteToFile(err.to_string()).into());
+        }
+
+        Ok(format!("Generated the man pages in {}", output.display()))
+    }
+}
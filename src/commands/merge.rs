@@ -0,0 +1,309 @@
+use super::{completions, Command};
+use crate::config::Config;
+use crate::error::{CommandError, ExecutionError};
+use crate::git::{self, GitCommandExecuterBuilder};
+use crate::manifest::Manifest;
+use crate::message::MessageArgs;
+use crate::paths;
+use crate::permissions;
+use crate::state::{self, Change, State};
+use crate::utils;
+use clap::Args;
+use clap_complete::ArgValueCandidates;
+use colored::Colorize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command as Process;
+use std::slice;
+use std::time::SystemTime;
+use thiserror::Error;
+
+/// Merge tools that can be set by their name
+const MERGE_TOOLS: &[(&str, &str)] = &[
+    ("vimdiff", "vim -d {merged} {live} {base} {repository}"),
+    ("nvimdiff", "nvim -d {merged} {live} {base} {repository}"),
+    ("meld", "meld {live} {base} {repository} --output {merged}"),
+];
+
+/// Marker that git merge-file writes at the start of every conflict
+const CONFLICT_MARKER: &[u8] = b"<<<<<<< live";
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("You can just merge one file at a time")]
+    MoreThanOneFile,
+    #[error("File does not exist in its destination")]
+    FileDoesNotExists,
+    #[error("The file is the same in its destination and in the repository")]
+    NothingToMerge,
+    #[error("The merge was aborted, the files were left in {0:?}")]
+    Aborted(PathBuf),
+    #[error("The file still has conflicts, the merge was left in {0:?}")]
+    UnresolvedConflicts(PathBuf),
+    #[error("You need to set a remote repository before use dfmn")]
+    SetRemoteRepository,
+    #[error("No internet connection")]
+    NoInternetConnection,
+}
+
+impl From<Error> for CommandError {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::NoInternetConnection => CommandError::Network(err.to_string()),
+            err => CommandError::Usage(err.to_string()),
+        }
+    }
+}
+
+/// Merges the changes of the live file and of the repository version of a file
+/// and commits the result
+#[derive(Debug, Args)]
+pub struct Merge {
+    /// File name or path
+    #[arg(add = ArgValueCandidates::new(completions::tracked_names))]
+    name: String,
+    /// Merge tool to use instead of the one of the config
+    #[arg(short, long)]
+    tool: Option<String>,
+    #[command(flatten)]
+    message: MessageArgs,
+}
+
+/// The versions of the file handed to the merge tool
+struct MergeFiles {
+    base: PathBuf,
+    live: PathBuf,
+    repository: PathBuf,
+    merged: PathBuf,
+}
+
+impl MergeFiles {
+    /// Writes the versions in a folder of their own, keeping the name of the
+    /// file at the end so the tools can highlight its syntax
+    fn write(
+        folder_path: &Path,
+        file_name: &str,
+        base: &[u8],
+        live: &[u8],
+        repository: &[u8],
+    ) -> Result<Self, ExecutionError> {
+        if let Err(err) = fs::create_dir_all(folder_path) {
+            return Err(ExecutionError::CreateFolder(err.to_string()));
+        }
+
+        let files = MergeFiles {
+            base: folder_path.join(format!("BASE_{file_name}")),
+            live: folder_path.join(format!("LIVE_{file_name}")),
+            repository: folder_path.join(format!("REPOSITORY_{file_name}")),
+            merged: folder_path.join(format!("MERGED_{file_name}")),
+        };
+
+        for (path, content) in [
+            (&files.base, base),
+            (&files.live, live),
+            (&files.repository, repository),
+            (&files.merged, live),
+        ] {
+            if let Err(err) = fs::write(path, content) {
+                return Err(ExecutionError::WriteToFile(err.to_string()));
+            }
+        }
+
+        Ok(files)
+    }
+}
+
+fn get_modified_time(path: &Path) -> Result<SystemTime, ExecutionError> {
+    match fs::metadata(path).and_then(|metadata| metadata.modified()) {
+        Ok(time) => Ok(time),
+        Err(err) => Err(ExecutionError::GetMetadata(err.to_string())),
+    }
+}
+
+fn read_file(path: &Path) -> Result<Vec<u8>, ExecutionError> {
+    match fs::read(path) {
+        Ok(content) => Ok(content),
+        Err(err) => Err(ExecutionError::ReadFile(err.to_string())),
+    }
+}
+
+/// Runs a merge tool, by its name or as a command with placeholders. Returns
+/// whether the tool exited successfully
+fn run_merge_tool(tool: &str, files: &MergeFiles) -> Result<bool, ExecutionError> {
+    let command = MERGE_TOOLS
+        .iter()
+        .find(|(name, _)| *name == tool)
+        .map_or(tool, |(_, command)| *command);
+
+    let words: Vec<String> = command
+        .split_whitespace()
+        .map(|word| {
+            word.replace("{base}", &files.base.to_string_lossy())
+                .replace("{live}", &files.live.to_string_lossy())
+                .replace("{repository}", &files.repository.to_string_lossy())
+                .replace("{merged}", &files.merged.to_string_lossy())
+        })
+        .collect();
+
+    let Some((program, args)) = words.split_first() else {
+        return Err(ExecutionError::OpenMergeTool(
+            "empty merge tool".to_string(),
+        ));
+    };
+
+    match Process::new(program).args(args).status() {
+        Ok(status) => Ok(status.success()),
+        Err(err) => Err(ExecutionError::OpenMergeTool(err.to_string())),
+    }
+}
+
+impl Command for Merge {
+    fn execute(self) -> Result<String, CommandError> {
+        if online::check(None).is_err() {
+            return Err(Error::NoInternetConnection.into());
+        }
+
+        let git_storage_folder_path = match utils::get_git_storage_folder_path() {
+            Ok(path) => path,
+            Err(err) => {
+                return Err(ExecutionError::GetStorageFolderPath(err.to_string()).into());
+            }
+        };
+
+        let git_storage_folder_path = match git_storage_folder_path.canonicalize() {
+            Ok(path) => path,
+            Err(err) => {
+                return Err(ExecutionError::CanonicalizePath(err.to_string()).into());
+            }
+        };
+
+        if utils::check_if_remote_link_is_added().is_err() {
+            return Err(Error::SetRemoteRepository.into());
+        }
+
+        let mut manifest = Manifest::load(&git_storage_folder_path)?;
+
+        let (name, live_file_path) =
+            match paths::resolve_tracked(&git_storage_folder_path, &manifest, &[self.name])?
+                .as_slice()
+            {
+                [file] => file.clone(),
+                _ => return Err(Error::MoreThanOneFile.into()),
+            };
+
+        if !live_file_path.is_file() {
+            return Err(Error::FileDoesNotExists.into());
+        }
+
+        let mut state = State::load()?;
+        let base_hash = state.hash(&name).map(str::to_string);
+
+        if state::classify(
+            &git_storage_folder_path,
+            base_hash.as_deref(),
+            &name,
+            &live_file_path,
+        )? == Change::InSync
+        {
+            return Err(Error::NothingToMerge.into());
+        }
+
+        // Without the version that was last deployed everything is merged
+        // against an empty file, so every difference is a conflict to review
+        let base = match &base_hash {
+            Some(hash) => git::get_blob(&git_storage_folder_path, hash)?.unwrap_or_default(),
+            None => Vec::new(),
+        };
+
+        let stored_file_path = git_storage_folder_path.join(&name);
+        let merge_folder_path = utils::get_dfm_folder_path()?.join("merge");
+        let file_name = name.rsplit('/').next().unwrap_or(&name);
+
+        let files = MergeFiles::write(
+            &merge_folder_path,
+            file_name,
+            &base,
+            &read_file(&live_file_path)?,
+            &read_file(&stored_file_path)?,
+        )?;
+
+        let tool = match self.tool {
+            Some(tool) => Some(tool),
+            None => Config::load()?.merge.tool,
+        };
+
+        match tool {
+            Some(tool) => {
+                let modified_time = get_modified_time(&files.merged)?;
+
+                // The merged file starts as the live one, so like git mergetool
+                // a tool closed without saving it did not merge anything
+                if !run_merge_tool(&tool, &files)?
+                    || get_modified_time(&files.merged)? == modified_time
+                {
+                    return Err(Error::Aborted(merge_folder_path).into());
+                }
+            }
+            None => {
+                if !git::merge_file(
+                    &git_storage_folder_path,
+                    &files.merged,
+                    &files.base,
+                    &files.repository,
+                )? {
+                    utils::open_editor(&files.merged)?;
+                }
+            }
+        }
+
+        let merged = read_file(&files.merged)?;
+
+        if merged
+            .windows(CONFLICT_MARKER.len())
+            .any(|window| window == CONFLICT_MARKER)
+        {
+            return Err(Error::UnresolvedConflicts(files.merged).into());
+        }
+
+        // Keeping the repository version only needs the live file to be replaced
+        let message = if merged != read_file(&stored_file_path)? {
+            Some(self.message.build("Merge", slice::from_ref(&name))?)
+        } else {
+            None
+        };
+
+        if let Err(err) = fs::write(&live_file_path, &merged) {
+            return Err(ExecutionError::WriteToFile(err.to_string()).into());
+        }
+
+        if let Some(message) = message {
+            if let Err(err) = fs::write(&stored_file_path, &merged) {
+                return Err(ExecutionError::WriteToFile(err.to_string()).into());
+            }
+
+            permissions::record(
+                manifest.files.entry(name.clone()).or_default(),
+                &live_file_path,
+                false,
+            )?;
+
+            manifest.save(&git_storage_folder_path)?;
+
+            GitCommandExecuterBuilder::new(&git_storage_folder_path)
+                .run_commit(message)
+                .build()
+                .run()?;
+        }
+
+        state.record(&git_storage_folder_path, &name, &live_file_path)?;
+        state.save()?;
+
+        if let Err(err) = fs::remove_dir_all(&merge_folder_path) {
+            return Err(ExecutionError::RemoveFile(err.to_string()).into());
+        }
+
+        println!("{} {}", "merged".green(), name);
+
+        Ok("Successfully merged the file and synchronized the local repository with the remote repository".to_string())
+    }
+}
@@ -1,51 +1,101 @@
 mod add;
+mod adopt;
 mod clone;
+mod completions;
+mod doctor;
+mod edit;
+mod fetch;
+mod init;
 mod list;
+mod log;
+mod man;
+mod merge;
+mod mv;
 mod remote;
 mod remove;
 mod reset;
+mod show;
 mod sync;
+mod ui;
 mod update;
+mod watch;
 
-use crate::{error::CommandError, utils::write_to_log_file};
+use crate::error::CommandError;
+use crate::logger;
 pub use add::Add;
+pub use adopt::Adopt;
 pub use clone::Clone;
 use colored::Colorize;
+pub use completions::{Completions, COMPLETE_VAR};
+pub use doctor::Doctor;
+pub use edit::Edit;
+pub use fetch::Fetch;
+pub use init::Init;
 pub use list::List;
+pub use log::Log;
+pub use man::{long_help, Man};
+pub use merge::Merge;
+pub use mv::Mv;
 pub use remote::Remote;
 pub use remove::Remove;
 pub use reset::Reset;
+pub use show::Show;
 pub use sync::Sync;
+pub use ui::Ui;
 pub use update::Update;
+pub use watch::Watch;
 
 pub trait Command: Sized {
     fn execute(self) -> Result<String, CommandError>;
 
-    fn error(err: CommandError) {
-        if let CommandError::Usage(err) = err {
-            eprintln!("{}", err.red());
+    fn error(err: &CommandError) {
+        match err {
+            CommandError::Usage(err) | CommandError::Network(err) | CommandError::Git(err) => {
+                eprintln!("{}", err.red());
 
-            return;
-        }
+                return;
+            }
+            CommandError::Conflict(err) => {
+                eprintln!("{}", err.red());
+                eprintln!(
+                    "{}",
+                    "Resolve the conflict in the local repository, ~/.config/dfmn/dotfiles, and try again".yellow()
+                );
+            }
+            CommandError::Execution(err) => {
+                eprintln!(
+                    "{}",
+                    "Something goes wrong during the command executation, please try again or run it with -v to see what happened".red()
+                );
 
-        eprintln!(
-            "{}",
-            "Something goes wrong during the command executation, please try again".red()
-        );
-
-        if let Err(err) = write_to_log_file(&err.to_string()) {
-            eprintln!(
-                "{}: {}",
-                "Error while trying to write in the log file".red(),
-                err.to_string().red()
-            );
+                // The output of the failed command, like the one of a git
+                // hook, may not show up again when retrying
+                for line in err.to_string().lines() {
+                    eprintln!("    {}", line.red());
+                }
+            }
         }
+
+        // The log module of this crate shadows the log crate here
+        ::log::error!("{}", logger::error_chain(err));
     }
 
-    fn call(self) {
+    /// Executes the command and reports its outcome, returning the error so the
+    /// process can exit with the matching code
+    fn call(self) -> Result<(), CommandError> {
         match self.execute() {
-            Ok(message) => println!("{}", message.green()),
-            Err(err) => Self::error(err),
+            Ok(message) => {
+                if !message.is_empty() {
+                    println!("{}", message.green());
+                }
+
+                Ok(())
+            }
+            Err(err) => {
+                Self::error(&err);
+
+                Err(err)
+            }
         }
     }
 }
@@ -0,0 +1,171 @@
+use super::{completions, Command};
+use crate::error::{CommandError, ExecutionError};
+use crate::git::{self, GitCommandExecuterBuilder};
+use crate::manifest::Manifest;
+use crate::message::MessageArgs;
+use crate::paths;
+use crate::state::State;
+use crate::utils;
+use clap::{Args, ValueHint};
+use clap_complete::{ArgValueCandidates, ArgValueCompleter, PathCompleter};
+use colored::Colorize;
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("You can just move one file at a time")]
+    MoreThanOneFile,
+    #[error("The file already has that name")]
+    SameName,
+    #[error("{0:?} is already tracked by dfmn")]
+    AlreadyTracked(String),
+    #[error("{0:?} already exists, move it away or drop --live")]
+    DestinationExists(String),
+    #[error("You need to set a remote repository before use dfmn")]
+    SetRemoteRepository,
+    #[error("No internet connection")]
+    NoInternetConnection,
+}
+
+impl From<Error> for CommandError {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::NoInternetConnection => CommandError::Network(err.to_string()),
+            err => CommandError::Usage(err.to_string()),
+        }
+    }
+}
+
+/// Renames or moves a tracked file, keeping its history
+#[derive(Debug, Args)]
+pub struct Mv {
+    /// File name or path
+    #[arg(add = ArgValueCandidates::new(completions::tracked_names))]
+    old: String,
+    /// New path of the file
+    #[arg(value_hint = ValueHint::AnyPath, add = ArgValueCompleter::new(PathCompleter::any()))]
+    new: String,
+    /// Also moves the file in its destination
+    #[arg(short, long)]
+    live: bool,
+    /// Allows the new path to be outside of your home folder
+    #[arg(short, long)]
+    force: bool,
+    #[command(flatten)]
+    message: MessageArgs,
+}
+
+/// Creates the folders that will hold a file
+fn create_parent_folder(path: &Path) -> Result<(), ExecutionError> {
+    if let Some(parent) = path.parent() {
+        if let Err(err) = fs::create_dir_all(parent) {
+            return Err(ExecutionError::CreateFolder(err.to_string()));
+        }
+    }
+
+    Ok(())
+}
+
+impl Command for Mv {
+    fn execute(self) -> Result<String, CommandError> {
+        if online::check(None).is_err() {
+            return Err(Error::NoInternetConnection.into());
+        }
+
+        let git_storage_folder_path = match utils::get_git_storage_folder_path() {
+            Ok(path) => path,
+            Err(err) => {
+                return Err(ExecutionError::GetStorageFolderPath(err.to_string()).into());
+            }
+        };
+
+        let git_storage_folder_path = match git_storage_folder_path.canonicalize() {
+            Ok(path) => path,
+            Err(err) => {
+                return Err(ExecutionError::CanonicalizePath(err.to_string()).into());
+            }
+        };
+
+        if utils::check_if_remote_link_is_added().is_err() {
+            return Err(Error::SetRemoteRepository.into());
+        }
+
+        let mut manifest = Manifest::load(&git_storage_folder_path)?;
+
+        let (old_name, old_live_file_path) =
+            match paths::resolve_tracked(&git_storage_folder_path, &manifest, &[self.old])?
+                .as_slice()
+            {
+                [file] => file.clone(),
+                _ => return Err(Error::MoreThanOneFile.into()),
+            };
+
+        let new_live_file_path = paths::resolve(&self.new)?;
+        let new_name = paths::get_name(&new_live_file_path, self.force)?;
+
+        if new_name == old_name {
+            return Err(Error::SameName.into());
+        }
+
+        let new_stored_file_path = git_storage_folder_path.join(&new_name);
+
+        if new_stored_file_path.exists() {
+            return Err(Error::AlreadyTracked(new_name).into());
+        }
+
+        // The live file is moved only when it exists, a file that was not
+        // deployed on this machine is just renamed in the repository
+        let move_live_file = self.live && old_live_file_path.exists();
+
+        if move_live_file && new_live_file_path.exists() {
+            return Err(Error::DestinationExists(new_live_file_path.display().to_string()).into());
+        }
+
+        let message = self
+            .message
+            .build("Move", &[format!("{old_name} to {new_name}")])?;
+
+        create_parent_folder(&new_stored_file_path)?;
+        git::move_file(&git_storage_folder_path, &old_name, &new_name)?;
+
+        // The permissions and hooks follow the file, while its destination is
+        // recorded again for the new path
+        if let Some(mut entry) = manifest.files.remove(&old_name) {
+            entry.destination = None;
+            manifest.files.insert(new_name.clone(), entry);
+        }
+
+        manifest.set_destination(&new_name, &new_live_file_path)?;
+        manifest.save(&git_storage_folder_path)?;
+
+        GitCommandExecuterBuilder::new(&git_storage_folder_path)
+            .run_commit(message)
+            .build()
+            .run()?;
+
+        let mut state = State::load()?;
+        state.rename(&old_name, &new_name);
+        state.save()?;
+
+        println!("{} {} -> {}", "moved".green(), old_name, new_name);
+
+        if move_live_file {
+            create_parent_folder(&new_live_file_path)?;
+
+            if let Err(err) = fs::rename(&old_live_file_path, &new_live_file_path) {
+                return Err(ExecutionError::RenameFile(err.to_string()).into());
+            }
+
+            println!(
+                "{} {} -> {}",
+                "moved".green(),
+                old_live_file_path.display(),
+                new_live_file_path.display()
+            );
+        }
+
+        Ok("Successfully moved the file and synchronized the local repository with the remote repository".to_string())
+    }
+}
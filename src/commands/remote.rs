@@ -27,7 +27,10 @@ pub enum Error {
 
 impl From<Error> for CommandError {
     fn from(err: Error) -> Self {
-        CommandError::Usage(err.to_string())
+        match err {
+            Error::NoInternetConnection => CommandError::Network(err.to_string()),
+            err => CommandError::Usage(err.to_string()),
+        }
     }
 }
 
@@ -53,7 +56,7 @@ pub enum Subcommands {
     Add(Add),
 }
 
-fn add_remote_link(
+pub(super) fn add_remote_link(
     storage_folder_path: &Path,
     git_storage_folder_path: &Path,
     link: &str,
@@ -1,15 +1,18 @@
-use super::Command;
+use super::{completions, Command};
 use crate::error::{CommandError, ExecutionError};
 use crate::git::GitCommandExecuterBuilder;
+use crate::manifest::Manifest;
+use crate::message::MessageArgs;
+use crate::paths;
+use crate::state::State;
 use crate::utils;
 use clap::Args;
+use clap_complete::ArgValueCandidates;
 use std::fs;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum Error {
-    #[error("File does not exist in the repository")]
-    FileDoesNotExists,
     #[error("You need to set a remote repository before use dfmn")]
     SetRemoteRepository,
     #[error("No internet connection")]
@@ -18,15 +21,32 @@ pub enum Error {
 
 impl From<Error> for CommandError {
     fn from(err: Error) -> Self {
-        CommandError::Usage(err.to_string())
+        match err {
+            Error::NoInternetConnection => CommandError::Network(err.to_string()),
+            err => CommandError::Usage(err.to_string()),
+        }
     }
 }
 
-/// Removes a file from the remote repository
+/// Removes files from the remote repository
 #[derive(Debug, Args)]
 pub struct Remove {
-    /// File name
-    name: String,
+    /// File names or paths, accepts glob patterns over the names relative to
+    /// your home folder, like '.config/nvim/**'
+    #[arg(add = ArgValueCandidates::new(completions::tracked_names), required = true)]
+    names: Vec<String>,
+    #[command(flatten)]
+    message: MessageArgs,
+}
+
+impl Remove {
+    /// Removes the given files with the generated commit message
+    pub(super) fn new(names: Vec<String>) -> Self {
+        Remove {
+            names,
+            message: MessageArgs::default(),
+        }
+    }
 }
 
 impl Command for Remove {
@@ -53,19 +73,34 @@ impl Command for Remove {
             return Err(Error::SetRemoteRepository.into());
         }
 
-        if !utils::check_if_file_exists(&git_storage_folder_path, &self.name) {
-            return Err(Error::FileDoesNotExists.into());
-        }
+        let mut manifest = Manifest::load(&git_storage_folder_path)?;
+        let names: Vec<String> =
+            paths::resolve_tracked(&git_storage_folder_path, &manifest, &self.names)?
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect();
 
-        if let Err(err) = fs::remove_file(git_storage_folder_path.join(&self.name)) {
-            return Err(ExecutionError::RemoveFile(err.to_string()).into());
+        let message = self.message.build("Remove", &names)?;
+        let mut state = State::load()?;
+
+        for name in &names {
+            if let Err(err) = fs::remove_file(git_storage_folder_path.join(name)) {
+                return Err(ExecutionError::RemoveFile(err.to_string()).into());
+            }
+
+            manifest.files.remove(name);
+            state.forget(name);
         }
 
+        manifest.save(&git_storage_folder_path)?;
+
         GitCommandExecuterBuilder::new(&git_storage_folder_path)
-            .run_commit(&format!("Remove {}", self.name))
+            .run_commit(message)
             .build()
             .run()?;
 
-        Ok("Successfully removed the file and synchronized the local repository with the remote repository".to_string())
+        state.save()?;
+
+        Ok("Successfully removed the file(s) and synchronized the local repository with the remote repository".to_string())
     }
 }
@@ -1,6 +1,7 @@
 use super::Command;
 use crate::error::{CommandError, ExecutionError};
 use crate::setup;
+use crate::state::STATE_FILE_NAME;
 use crate::utils;
 use clap::Args;
 use std::fs;
@@ -57,6 +58,15 @@ impl Command for Reset {
             return Err(ExecutionError::RemoveFile(err.to_string()).into());
         }
 
+        // The recorded hashes belong to the files of the old repository
+        let state_path = storage_folder_path.join(STATE_FILE_NAME);
+
+        if state_path.exists() {
+            if let Err(err) = fs::remove_file(state_path) {
+                return Err(ExecutionError::RemoveFile(err.to_string()).into());
+            }
+        }
+
         if let Err(err) = fs::create_dir_all(&git_storage_folder_path) {
             return Err(ExecutionError::CreateStorageFolder(err.to_string()).into());
         }
@@ -0,0 +1,107 @@
+use super::{completions, Command};
+use crate::error::{CommandError, ExecutionError};
+use crate::git;
+use crate::manifest::Manifest;
+use crate::paths;
+use crate::utils;
+use clap::Args;
+use clap_complete::ArgValueCandidates;
+use std::fs;
+use std::io::{self, Write};
+use std::slice;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("You can just show one file at a time")]
+    MoreThanOneFile,
+    #[error("{0:?} is not a revision of the repository")]
+    InvalidRevision(String),
+    #[error("{name:?} is not in the repository at {revision:?}")]
+    NotInRevision { name: String, revision: String },
+}
+
+impl From<Error> for CommandError {
+    fn from(err: Error) -> Self {
+        CommandError::Usage(err.to_string())
+    }
+}
+
+/// Prints the repository version of a file, or the one of a past revision
+#[derive(Debug, Args)]
+pub struct Show {
+    /// File name or path
+    #[arg(add = ArgValueCandidates::new(completions::tracked_names))]
+    name: String,
+    /// Revision to read the file from, like a commit hash or HEAD~2
+    #[arg(short, long)]
+    rev: Option<String>,
+    /// Prints the file instead of opening it in your pager
+    #[arg(long)]
+    no_pager: bool,
+}
+
+impl Command for Show {
+    fn execute(self) -> Result<String, CommandError> {
+        let git_storage_folder_path = match utils::get_git_storage_folder_path() {
+            Ok(path) => path,
+            Err(err) => {
+                return Err(ExecutionError::GetStorageFolderPath(err.to_string()).into());
+            }
+        };
+
+        let git_storage_folder_path = match git_storage_folder_path.canonicalize() {
+            Ok(path) => path,
+            Err(err) => {
+                return Err(ExecutionError::CanonicalizePath(err.to_string()).into());
+            }
+        };
+
+        let manifest = Manifest::load(&git_storage_folder_path)?;
+
+        let args = slice::from_ref(&self.name);
+
+        // Files that were renamed or removed can still be read from the
+        // revisions that had them
+        let files = if self.rev.is_some() {
+            paths::resolve_names(&git_storage_folder_path, &manifest, args)?
+        } else {
+            paths::resolve_tracked(&git_storage_folder_path, &manifest, args)?
+        };
+
+        let name = match files.as_slice() {
+            [(name, _)] => name.clone(),
+            _ => return Err(Error::MoreThanOneFile.into()),
+        };
+
+        let content = match self.rev {
+            Some(revision) => {
+                if !git::revision_exists(&git_storage_folder_path, &revision)? {
+                    return Err(Error::InvalidRevision(revision).into());
+                }
+
+                match git::get_file_at(&git_storage_folder_path, &revision, &name)? {
+                    Some(content) => content,
+                    None => return Err(Error::NotInRevision { name, revision }.into()),
+                }
+            }
+            None => match fs::read(git_storage_folder_path.join(&name)) {
+                Ok(content) => content,
+                Err(err) => {
+                    return Err(ExecutionError::ReadFile(err.to_string()).into());
+                }
+            },
+        };
+
+        if self.no_pager {
+            if let Err(err) = io::stdout().write_all(&content) {
+                return Err(ExecutionError::WriteToFile(err.to_string()).into());
+            }
+        } else {
+            utils::page(&content, &name)?;
+        }
+
+        // Nothing else is printed so the output can be piped
+        Ok(String::new())
+    }
+}
@@ -1,8 +1,16 @@
+use super::fetch::{fetch_incoming, print_incoming, print_incoming_diff};
 use super::Command;
+use crate::deploy::{self, Outcome};
+use crate::dfmnignore::IgnoreMatcher;
 use crate::error::{CommandError, ExecutionError};
-use crate::git::GitCommandExecuterBuilder;
+use crate::git::{self, GitCommandExecuterBuilder};
+use crate::hooks::{self, Context, Event, Stage};
+use crate::manifest::Manifest;
+use crate::state::{self, Change, State};
 use crate::utils;
 use clap::Args;
+use colored::Colorize;
+use std::path::Path;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -15,13 +23,134 @@ pub enum Error {
 
 impl From<Error> for CommandError {
     fn from(err: Error) -> Self {
-        CommandError::Usage(err.to_string())
+        match err {
+            Error::NoInternetConnection => CommandError::Network(err.to_string()),
+            err => CommandError::Usage(err.to_string()),
+        }
     }
 }
 
-/// Syncs the files with the remote repository
+/// Syncs the files with the remote repository, pushing the local commits and
+/// deploying the files that changed
 #[derive(Debug, Args)]
-pub struct Sync;
+pub struct Sync {
+    /// Shows the files that would change and their diffs against your live
+    /// files, without applying the changes
+    #[arg(long, conflicts_with = "interactive")]
+    preview: bool,
+    /// Asks whether to accept or skip the changes of every file
+    #[arg(short, long)]
+    interactive: bool,
+    /// Only synchronizes the repository, without deploying the changed files
+    #[arg(long)]
+    no_deploy: bool,
+    /// Does not run the hooks
+    #[arg(long)]
+    no_hooks: bool,
+}
+
+/// Pushes the local commits that are not in the remote repository yet, like
+/// the ones made offline
+fn push_local_commits(git_storage_folder_path: &Path) -> Result<(), CommandError> {
+    let has_local_commits = match git::count_unpushed_commits(git_storage_folder_path)? {
+        Some(count) => count > 0,
+        // The remote repository is empty
+        None => git::get_head(git_storage_folder_path)?.is_some(),
+    };
+
+    if has_local_commits {
+        GitCommandExecuterBuilder::new(git_storage_folder_path)
+            .run_push()
+            .build()
+            .run()?;
+    }
+
+    Ok(())
+}
+
+/// Returns the files changed by the synchronization, leaving out the ignored ones
+fn get_pulled_files(
+    git_storage_folder_path: &Path,
+    previous_head: Option<&str>,
+) -> Result<Vec<String>, CommandError> {
+    let Some(head) = git::get_head(git_storage_folder_path)? else {
+        return Ok(Vec::new());
+    };
+
+    if previous_head == Some(head.as_str()) {
+        return Ok(Vec::new());
+    }
+
+    let ignore_matcher = IgnoreMatcher::load(git_storage_folder_path)?;
+
+    Ok(
+        git::get_changed_files(git_storage_folder_path, previous_head, &head)?
+            .into_iter()
+            .filter(|name| !ignore_matcher.is_ignored(name, false))
+            .collect(),
+    )
+}
+
+/// Deploys a file changed by the synchronization, unless its live version was
+/// also edited since it was deployed. The files deployed before the state was
+/// recorded are compared with the version the repository had before
+fn deploy_pulled_file(
+    git_storage_folder_path: &Path,
+    manifest: &Manifest,
+    state: &State,
+    previous_head: Option<&str>,
+    name: &str,
+    no_hooks: bool,
+) -> Result<Outcome, CommandError> {
+    let destination = manifest.destination(name)?;
+
+    let base = match (state.hash(name), previous_head) {
+        (Some(hash), _) => Some(hash.to_string()),
+        (None, Some(previous_head)) => {
+            git::get_blob_hash(git_storage_folder_path, previous_head, name)?
+        }
+        (None, None) => None,
+    };
+
+    if destination.exists() {
+        match state::classify(git_storage_folder_path, base.as_deref(), name, &destination)? {
+            Change::InSync | Change::Remote | Change::Missing => {}
+            Change::Local | Change::Both | Change::Unknown => return Ok(Outcome::Skipped),
+        }
+    }
+
+    deploy::deploy_tracked_file(git_storage_folder_path, manifest, name, true, no_hooks)
+}
+
+/// Asks whether to accept the incoming changes of every file, returning the
+/// files that are not deployed on this machine
+fn review_incoming(git_storage_folder_path: &Path) -> Result<Vec<String>, CommandError> {
+    let manifest = Manifest::load(git_storage_folder_path)?;
+    let mut skipped = Vec::new();
+
+    for incoming in fetch_incoming(git_storage_folder_path)? {
+        print_incoming(&incoming);
+
+        loop {
+            // Without anybody to answer the live file is left as it is
+            let answer = utils::prompt("[a]ccept, [s]kip or show [d]iff?")?;
+
+            match answer.as_deref() {
+                Some("a" | "accept") => break,
+                Some("s" | "skip") | None => {
+                    skipped.push(incoming.name);
+                    break;
+                }
+                Some("d" | "diff") => {
+                    print_incoming_diff(git_storage_folder_path, &manifest, &incoming)?
+                }
+                _ => continue,
+            }
+        }
+    }
+
+    Ok(skipped)
+}
 
 impl Command for Sync {
     fn execute(self) -> Result<String, CommandError> {
@@ -47,11 +176,117 @@ impl Command for Sync {
             return Err(Error::SetRemoteRepository.into());
         }
 
+        if self.preview {
+            let manifest = Manifest::load(&git_storage_folder_path)?;
+            let incoming = fetch_incoming(&git_storage_folder_path)?;
+
+            if incoming.is_empty() {
+                return Ok("Already up to date with the remote repository".to_string());
+            }
+
+            for incoming in &incoming {
+                print_incoming(incoming);
+                print_incoming_diff(&git_storage_folder_path, &manifest, incoming)?;
+            }
+
+            return Ok(format!(
+                "{} file(s) will change, run dfmn sync to apply them",
+                incoming.len()
+            ));
+        }
+
+        if !self.no_hooks {
+            let manifest = Manifest::load(&git_storage_folder_path)?;
+
+            hooks::run(
+                &manifest.hooks,
+                Stage::Pre,
+                Event::Sync,
+                &Context::default(),
+            )?;
+        }
+
+        let previous_head = git::get_head(&git_storage_folder_path)?;
+
+        // A repository without commits has no live files to keep
+        let skipped_files = if self.interactive && previous_head.is_some() {
+            review_incoming(&git_storage_folder_path)?
+        } else {
+            Vec::new()
+        };
+
         GitCommandExecuterBuilder::new(&git_storage_folder_path)
             .run_pull()
             .build()
             .run()?;
 
+        push_local_commits(&git_storage_folder_path)?;
+
+        let manifest = Manifest::load(&git_storage_folder_path)?;
+        // The files skipped while reviewing stay as they are on this machine
+        let changed_files: Vec<String> =
+            get_pulled_files(&git_storage_folder_path, previous_head.as_deref())?
+                .into_iter()
+                .filter(|name| !skipped_files.contains(name))
+                .collect();
+        let state = State::load()?;
+        let mut skipped = 0;
+
+        if !self.no_deploy {
+            let tracked_files = utils::get_tracked_files(&git_storage_folder_path)?;
+
+            for name in changed_files
+                .iter()
+                .filter(|name| tracked_files.contains(name))
+            {
+                let destination = manifest.destination(name)?;
+
+                match deploy_pulled_file(
+                    &git_storage_folder_path,
+                    &manifest,
+                    &state,
+                    previous_head.as_deref(),
+                    name,
+                    self.no_hooks,
+                )? {
+                    Outcome::Deployed | Outcome::BackedUp(_) => {
+                        println!("{} {}", "deployed".green(), destination.display());
+                    }
+                    Outcome::UpToDate => {}
+                    Outcome::Skipped => {
+                        println!(
+                            "{} {} (changed locally)",
+                            "skipped".yellow(),
+                            destination.display()
+                        );
+
+                        skipped += 1;
+                    }
+                }
+            }
+        }
+
+        if !self.no_hooks {
+            hooks::run(
+                &manifest.hooks,
+                Stage::Post,
+                Event::Sync,
+                &Context::default(),
+            )?;
+
+            for name in &changed_files {
+                let destination = manifest.destination(name)?;
+
+                hooks::run_for_file(&manifest, name, &destination, Stage::Post, Event::Sync)?;
+            }
+        }
+
+        if skipped > 0 {
+            return Ok(format!(
+                "Finished the synchronization with the remote repository, {skipped} file(s) were not deployed since they changed locally, run dfmn merge to combine the changes"
+            ));
+        }
+
         Ok("Finished the synchronization with the remote repository".to_string())
     }
 }
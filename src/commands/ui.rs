@@ -0,0 +1,474 @@
+use super::{Command, Remove, Update};
+use crate::deploy::{self, Outcome};
+use crate::error::{CommandError, ExecutionError};
+use crate::git;
+use crate::manifest::Manifest;
+use crate::utils;
+use clap::Args;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Style, Stylize};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const HELP: &str = "j/k move  J/K scroll  u update  r restore  d deploy  x remove  e edit  q quit";
+
+/// Opens a terminal UI to browse and manage the tracked files
+#[derive(Debug, Args)]
+pub struct Ui;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Status {
+    Identical,
+    Modified,
+    Missing,
+}
+
+struct Entry {
+    name: String,
+    destination: PathBuf,
+    status: Status,
+}
+
+/// Actions that change the files and are confirmed before running
+#[derive(Clone, Copy)]
+enum Pending {
+    Restore,
+    Remove,
+}
+
+struct App {
+    git_storage_folder_path: PathBuf,
+    entries: Vec<Entry>,
+    list_state: ListState,
+    preview: String,
+    scroll: u16,
+    message: String,
+    pending: Option<Pending>,
+}
+
+/// Deploys a single file to its destination, so the UI can run it like the
+/// other commands
+struct Deploy {
+    name: String,
+    force: bool,
+}
+
+impl Command for Deploy {
+    fn execute(self) -> Result<String, CommandError> {
+        let git_storage_folder_path = match utils::get_git_storage_folder_path() {
+            Ok(path) => path,
+            Err(err) => {
+                return Err(ExecutionError::GetStorageFolderPath(err.to_string()).into());
+            }
+        };
+
+        let manifest = Manifest::load(&git_storage_folder_path)?;
+
+        let outcome = deploy::deploy_tracked_file(
+            &git_storage_folder_path,
+            &manifest,
+            &self.name,
+            self.force,
+            false,
+        )?;
+
+        Ok(match outcome {
+            Outcome::Deployed => format!("Deployed {}", self.name),
+            Outcome::BackedUp(backup_path) => format!(
+                "Deployed {}, the old version is at {}",
+                self.name,
+                backup_path.display()
+            ),
+            Outcome::UpToDate => format!("{} is up to date", self.name),
+            Outcome::Skipped => format!("Skipped {}", self.name),
+        })
+    }
+}
+
+/// Leaves the UI so a command can print and ask questions in the terminal
+fn suspend() -> Result<(), ExecutionError> {
+    if let Err(err) = terminal::disable_raw_mode() {
+        return Err(ExecutionError::Terminal(err.to_string()));
+    }
+
+    if let Err(err) = execute!(io::stdout(), LeaveAlternateScreen) {
+        return Err(ExecutionError::Terminal(err.to_string()));
+    }
+
+    Ok(())
+}
+
+fn resume(terminal: &mut DefaultTerminal) -> Result<(), ExecutionError> {
+    if let Err(err) = terminal::enable_raw_mode() {
+        return Err(ExecutionError::Terminal(err.to_string()));
+    }
+
+    if let Err(err) = execute!(io::stdout(), EnterAlternateScreen) {
+        return Err(ExecutionError::Terminal(err.to_string()));
+    }
+
+    if let Err(err) = terminal.clear() {
+        return Err(ExecutionError::Terminal(err.to_string()));
+    }
+
+    Ok(())
+}
+
+fn get_status(source: &Path, destination: &Path) -> Result<Status, ExecutionError> {
+    if !destination.is_file() {
+        return Ok(Status::Missing);
+    }
+
+    match utils::check_if_files_are_equal(source, destination) {
+        Ok(true) => Ok(Status::Identical),
+        Ok(false) => Ok(Status::Modified),
+        Err(err) => Err(ExecutionError::Unknown {
+            err: err.to_string(),
+            trying_to: "check if files are equal",
+        }),
+    }
+}
+
+impl App {
+    fn new(git_storage_folder_path: PathBuf) -> Result<Self, CommandError> {
+        let mut app = App {
+            git_storage_folder_path,
+            entries: Vec::new(),
+            list_state: ListState::default(),
+            preview: String::new(),
+            scroll: 0,
+            message: String::new(),
+            pending: None,
+        };
+
+        app.refresh()?;
+
+        Ok(app)
+    }
+
+    fn selected(&self) -> Option<&Entry> {
+        self.list_state
+            .selected()
+            .and_then(|index| self.entries.get(index))
+    }
+
+    /// Reads the tracked files and their status again, keeping the selection
+    fn refresh(&mut self) -> Result<(), CommandError> {
+        let manifest = Manifest::load(&self.git_storage_folder_path)?;
+        let selected_name = self.selected().map(|entry| entry.name.clone());
+
+        self.entries.clear();
+
+        for name in utils::get_tracked_files(&self.git_storage_folder_path)? {
+            let destination = manifest.destination(&name)?;
+            let status = get_status(&self.git_storage_folder_path.join(&name), &destination)?;
+
+            self.entries.push(Entry {
+                name,
+                destination,
+                status,
+            });
+        }
+
+        let index = selected_name
+            .and_then(|name| self.entries.iter().position(|entry| entry.name == name))
+            .unwrap_or(0);
+
+        self.list_state
+            .select((!self.entries.is_empty()).then_some(index));
+
+        self.load_preview()
+    }
+
+    /// Shows the changes of the live file, or the repository version when
+    /// there is nothing to compare it with
+    fn load_preview(&mut self) -> Result<(), CommandError> {
+        self.scroll = 0;
+
+        let Some(entry) = self.selected() else {
+            self.preview = String::new();
+
+            return Ok(());
+        };
+
+        let source = self.git_storage_folder_path.join(&entry.name);
+
+        self.preview = match entry.status {
+            Status::Modified => {
+                git::diff_files(&self.git_storage_folder_path, &source, &entry.destination)?
+            }
+            Status::Identical | Status::Missing => match fs::read(&source) {
+                Ok(content) => String::from_utf8_lossy(&content).to_string(),
+                Err(err) => {
+                    return Err(ExecutionError::ReadFile(err.to_string()).into());
+                }
+            },
+        };
+
+        Ok(())
+    }
+
+    fn select(&mut self, offset: isize) -> Result<(), CommandError> {
+        if self.entries.is_empty() {
+            return Ok(());
+        }
+
+        let index = self.list_state.selected().unwrap_or(0) as isize + offset;
+        let index = index.clamp(0, self.entries.len() as isize - 1) as usize;
+
+        self.list_state.select(Some(index));
+        self.load_preview()
+    }
+
+    /// Runs a command outside of the UI, waiting for the user to read its
+    /// output before coming back
+    fn run(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+        command: impl Command,
+        description: &str,
+    ) -> Result<(), CommandError> {
+        suspend()?;
+
+        let result = command.call();
+
+        utils::prompt("Press Enter to go back")?;
+        resume(terminal)?;
+
+        self.message = match result {
+            Ok(()) => format!("{description} finished"),
+            Err(_) => format!("{description} failed"),
+        };
+
+        self.refresh()
+    }
+
+    fn edit(&mut self, terminal: &mut DefaultTerminal) -> Result<(), CommandError> {
+        let Some(entry) = self.selected() else {
+            return Ok(());
+        };
+
+        let path = match entry.status {
+            Status::Missing => self.git_storage_folder_path.join(&entry.name),
+            Status::Identical | Status::Modified => entry.destination.clone(),
+        };
+
+        suspend()?;
+
+        let result = utils::open_editor(&path);
+
+        resume(terminal)?;
+        result?;
+
+        self.message = format!("Edited {}", path.display());
+
+        self.refresh()
+    }
+
+    /// Handles a key, returning `false` when the UI should be closed
+    fn handle_key(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+        key: KeyCode,
+    ) -> Result<bool, CommandError> {
+        let name = self.selected().map(|entry| entry.name.clone());
+
+        if let Some(pending) = self.pending.take() {
+            let Some(name) = name else {
+                return Ok(true);
+            };
+
+            if key != KeyCode::Char('y') {
+                self.message = "Cancelled".to_string();
+
+                return Ok(true);
+            }
+
+            match pending {
+                Pending::Restore => {
+                    self.run(terminal, Deploy { name, force: true }, "Restoring the file")?
+                }
+                Pending::Remove => {
+                    self.run(terminal, Remove::new(vec![name]), "Removing the file")?
+                }
+            }
+
+            return Ok(true);
+        }
+
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(false),
+            KeyCode::Char('j') | KeyCode::Down => self.select(1)?,
+            KeyCode::Char('k') | KeyCode::Up => self.select(-1)?,
+            KeyCode::Char('J') => self.scroll = self.scroll.saturating_add(1),
+            KeyCode::Char('K') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::Char('e') => self.edit(terminal)?,
+            _ => {
+                let Some(name) = name else {
+                    return Ok(true);
+                };
+
+                match key {
+                    KeyCode::Char('u') => {
+                        self.run(terminal, Update::new(vec![name]), "Updating the file")?
+                    }
+                    KeyCode::Char('d') => self.run(
+                        terminal,
+                        Deploy { name, force: false },
+                        "Deploying the file",
+                    )?,
+                    KeyCode::Char('r') => {
+                        self.message =
+                            format!("Overwrite {name} with the repository version? [y/n]");
+                        self.pending = Some(Pending::Restore);
+                    }
+                    KeyCode::Char('x') => {
+                        self.message = format!("Stop tracking {name}? [y/n]");
+                        self.pending = Some(Pending::Remove);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main_area, message_area, help_area] = Layout::vertical([
+            Constraint::Min(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let [list_area, preview_area] =
+            Layout::horizontal([Constraint::Percentage(35), Constraint::Percentage(65)])
+                .areas(main_area);
+
+        let items: Vec<ListItem> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let status = match entry.status {
+                    Status::Identical => Span::from("identical ").green(),
+                    Status::Modified => Span::from("modified  ").yellow(),
+                    Status::Missing => Span::from("missing   ").red(),
+                };
+
+                ListItem::new(Line::from(vec![status, Span::from(entry.name.as_str())]))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(Block::bordered().title(format!(" Tracked files ({}) ", self.entries.len())))
+            .highlight_style(Style::new().reversed());
+
+        frame.render_stateful_widget(list, list_area, &mut self.list_state);
+
+        let title = match self.selected() {
+            Some(entry) if entry.status == Status::Modified => {
+                format!(" Changes of {} ", entry.destination.display())
+            }
+            Some(entry) => format!(" {} ", entry.name),
+            None => " No tracked files ".to_string(),
+        };
+
+        let is_diff = matches!(self.selected(), Some(entry) if entry.status == Status::Modified);
+
+        let lines: Vec<Line> = self
+            .preview
+            .lines()
+            .map(|line| {
+                let text = Line::from(line.to_string());
+
+                if !is_diff {
+                    text
+                } else if line.starts_with("@@") {
+                    text.cyan()
+                } else if line.starts_with('+') {
+                    text.green()
+                } else if line.starts_with('-') {
+                    text.red()
+                } else {
+                    text
+                }
+            })
+            .collect();
+
+        frame.render_widget(
+            Paragraph::new(Text::from(lines))
+                .block(Block::bordered().title(title))
+                .scroll((self.scroll, 0)),
+            preview_area,
+        );
+
+        frame.render_widget(Paragraph::new(self.message.as_str()).yellow(), message_area);
+        frame.render_widget(Paragraph::new(HELP).dim(), help_area);
+    }
+}
+
+fn run_app(terminal: &mut DefaultTerminal, app: &mut App) -> Result<(), CommandError> {
+    loop {
+        if let Err(err) = terminal.draw(|frame| app.draw(frame)) {
+            return Err(ExecutionError::Terminal(err.to_string()).into());
+        }
+
+        let event = match event::read() {
+            Ok(event) => event,
+            Err(err) => {
+                return Err(ExecutionError::Terminal(err.to_string()).into());
+            }
+        };
+
+        let Event::Key(key) = event else {
+            continue;
+        };
+
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        if !app.handle_key(terminal, key.code)? {
+            return Ok(());
+        }
+    }
+}
+
+impl Command for Ui {
+    fn execute(self) -> Result<String, CommandError> {
+        let git_storage_folder_path = match utils::get_git_storage_folder_path() {
+            Ok(path) => path,
+            Err(err) => {
+                return Err(ExecutionError::GetStorageFolderPath(err.to_string()).into());
+            }
+        };
+
+        let git_storage_folder_path = match git_storage_folder_path.canonicalize() {
+            Ok(path) => path,
+            Err(err) => {
+                return Err(ExecutionError::CanonicalizePath(err.to_string()).into());
+            }
+        };
+
+        let mut app = App::new(git_storage_folder_path)?;
+        let mut terminal = ratatui::init();
+
+        let result = run_app(&mut terminal, &mut app);
+
+        ratatui::restore();
+
+        result?;
+
+        Ok(String::new())
+    }
+}
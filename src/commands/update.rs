@@ -1,22 +1,30 @@
-use super::Command;
+use super::{completions, Command};
 use crate::error::{CommandError, ExecutionError};
 use crate::git::GitCommandExecuterBuilder;
+use crate::hooks::{self, Context, Event, Stage};
+use crate::manifest::Manifest;
+use crate::message::MessageArgs;
+use crate::paths;
+use crate::permissions;
+use crate::state::{self, Change, State};
 use crate::utils;
 use clap::Args;
-use std::env;
-use std::fs::{self, File};
-use std::io::{BufReader, Read};
-use std::path::Path;
+use clap_complete::ArgValueCandidates;
+use colored::Colorize;
+use std::fs;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum Error {
-    #[error("File does not exist in the current folder")]
+    #[error("File does not exist in its destination")]
     FileDoesNotExists,
-    #[error("File not added")]
-    FileNotAdded,
     #[error("Nothing to update")]
     NothingToUpdate,
+    #[error("{0} changed both here and in the repository since it was deployed, run dfmn merge to combine the changes or use --force to overwrite the repository version")]
+    ChangedInBoth(String),
+    #[error("{0} has a newer repository version, run dfmn sync to deploy it or use --force to overwrite it")]
+    NewerInRepository(String),
     #[error("You need to set a remote repository before use dfmn")]
     SetRemoteRepository,
     #[error("No internet connection")]
@@ -25,38 +33,80 @@ pub enum Error {
 
 impl From<Error> for CommandError {
     fn from(err: Error) -> Self {
-        CommandError::Usage(err.to_string())
+        match err {
+            Error::NoInternetConnection => CommandError::Network(err.to_string()),
+            err => CommandError::Usage(err.to_string()),
+        }
     }
 }
 
-/// Updates a file from the remote repository
+/// Updates files from the remote repository
 #[derive(Debug, Args)]
 pub struct Update {
-    /// File name
-    name: String,
+    /// File names or paths, accepts glob patterns over the names relative to
+    /// your home folder, like '.config/nvim/**'
+    #[arg(add = ArgValueCandidates::new(completions::tracked_names), required_unless_present = "all", conflicts_with = "all")]
+    names: Vec<String>,
+    /// Updates every tracked file that changed in its destination
+    #[arg(short, long)]
+    all: bool,
+    /// Updates the files even if their repository version changed since they were deployed
+    #[arg(short, long)]
+    force: bool,
+    #[command(flatten)]
+    message: MessageArgs,
+    /// Records the modification time of the files to restore it when deploying them
+    #[arg(long)]
+    preserve_mtime: bool,
+    /// Does not run the hooks
+    #[arg(long)]
+    no_hooks: bool,
 }
 
-fn check_if_files_are_equal(first_file: &Path, second_file: &Path) -> Result<bool, std::io::Error> {
-    let first_file = File::open(first_file)?;
-    let second_file = File::open(second_file)?;
+impl Update {
+    /// Updates the given files with the generated commit message
+    pub(super) fn new(names: Vec<String>) -> Self {
+        Update {
+            names,
+            all: false,
+            force: false,
+            message: MessageArgs::default(),
+            preserve_mtime: false,
+            no_hooks: false,
+        }
+    }
+}
 
-    let first_file_metadata = first_file.metadata()?;
-    let second_file_metadata = second_file.metadata()?;
+/// Returns the live path of every file that will be checked for changes
+fn get_live_files(
+    names: &[String],
+    all: bool,
+    git_storage_folder_path: &Path,
+    manifest: &Manifest,
+) -> Result<Vec<(String, PathBuf)>, CommandError> {
+    if all {
+        let mut files = Vec::new();
+
+        for name in utils::get_tracked_files(git_storage_folder_path)? {
+            let destination = manifest.destination(&name)?;
+
+            if destination.is_file() {
+                files.push((name, destination));
+            }
+        }
 
-    if first_file_metadata.len() != second_file_metadata.len() {
-        return Ok(false);
+        return Ok(files);
     }
 
-    let first_file_reader = BufReader::new(first_file);
-    let second_file_reader = BufReader::new(second_file);
+    let files = paths::resolve_tracked(git_storage_folder_path, manifest, names)?;
 
-    for (b1, b2) in first_file_reader.bytes().zip(second_file_reader.bytes()) {
-        if b1? != b2? {
-            return Ok(false);
+    for (_, live_file_path) in &files {
+        if !live_file_path.is_file() {
+            return Err(Error::FileDoesNotExists.into());
         }
     }
 
-    Ok(true)
+    Ok(files)
 }
 
 impl Command for Update {
@@ -83,51 +133,102 @@ impl Command for Update {
             return Err(Error::SetRemoteRepository.into());
         }
 
-        let current_dir = match env::current_dir() {
-            Ok(path) => path,
-            Err(err) => {
-                return Err(ExecutionError::GetCurrentDir(err.to_string()).into());
+        let mut manifest = Manifest::load(&git_storage_folder_path)?;
+        let mut state = State::load()?;
+        let mut changed_files = Vec::new();
+        let mut changed_in_both = Vec::new();
+        let mut newer_in_repository = Vec::new();
+
+        for (name, live_file_path) in
+            get_live_files(&self.names, self.all, &git_storage_folder_path, &manifest)?
+        {
+            match state::classify(
+                &git_storage_folder_path,
+                state.hash(&name),
+                &name,
+                &live_file_path,
+            )? {
+                Change::InSync => {}
+                // The live file was not edited, the repository just has a newer version
+                Change::Remote if !self.force => newer_in_repository.push(name),
+                Change::Both if !self.force => changed_in_both.push(name),
+                _ => changed_files.push((name, live_file_path)),
             }
-        };
-
-        if !utils::check_if_file_exists(&current_dir, &self.name) {
-            return Err(Error::FileDoesNotExists.into());
         }
 
-        if !utils::check_if_file_exists(&git_storage_folder_path, &self.name) {
-            return Err(Error::FileNotAdded.into());
+        if !changed_in_both.is_empty() {
+            return Err(Error::ChangedInBoth(changed_in_both.join(", ")).into());
         }
 
-        let two_files_are_equal = match check_if_files_are_equal(
-            &current_dir.join(&self.name),
-            &git_storage_folder_path.join(&self.name),
-        ) {
-            Ok(result) => result,
-            Err(err) => {
-                return Err(ExecutionError::Unknown {
-                    err: err.to_string(),
-                    trying_to: "check if files are equal",
-                }
-                .into());
+        if changed_files.is_empty() {
+            if !newer_in_repository.is_empty() {
+                return Err(Error::NewerInRepository(newer_in_repository.join(", ")).into());
             }
-        };
 
-        if two_files_are_equal {
             return Err(Error::NothingToUpdate.into());
         }
 
-        if let Err(err) = fs::copy(
-            current_dir.join(&self.name),
-            git_storage_folder_path.join(&self.name),
-        ) {
-            return Err(ExecutionError::CopyFile(err.to_string()).into());
+        let names: Vec<String> = changed_files.iter().map(|(name, _)| name.clone()).collect();
+        let message = self.message.build("Update", &names)?;
+
+        let context = match changed_files.as_slice() {
+            [(name, live_file_path)] => Context {
+                file: Some(name),
+                destination: Some(live_file_path),
+            },
+            _ => Context::default(),
+        };
+
+        if !self.no_hooks {
+            hooks::run(&manifest.hooks, Stage::Pre, Event::Update, &context)?;
+
+            for (name, live_file_path) in &changed_files {
+                hooks::run_for_file(&manifest, name, live_file_path, Stage::Pre, Event::Update)?;
+            }
+        }
+
+        for (name, live_file_path) in &changed_files {
+            if let Err(err) = fs::copy(live_file_path, git_storage_folder_path.join(name)) {
+                return Err(ExecutionError::CopyFile(err.to_string()).into());
+            }
+
+            permissions::record(
+                manifest.files.entry(name.clone()).or_default(),
+                live_file_path,
+                self.preserve_mtime,
+            )?;
+            state.record(&git_storage_folder_path, name, live_file_path)?;
         }
 
+        manifest.save(&git_storage_folder_path)?;
+
         GitCommandExecuterBuilder::new(&git_storage_folder_path)
-            .run_commit(&format!("Update {}", self.name))
+            .run_commit(message)
             .build()
             .run()?;
 
-        Ok("Successfully updated the file and synchronized the local repository with the remote repository".to_string())
+        state.save()?;
+
+        if !self.no_hooks {
+            for (name, live_file_path) in &changed_files {
+                hooks::run_for_file(&manifest, name, live_file_path, Stage::Post, Event::Update)?;
+            }
+
+            hooks::run(&manifest.hooks, Stage::Post, Event::Update, &context)?;
+        }
+
+        for name in &names {
+            println!("{} {}", "updated".green(), name);
+        }
+
+        for name in &newer_in_repository {
+            println!(
+                "{} {} (newer repository version, run dfmn sync)",
+                "skipped".yellow(),
+                name
+            );
+        }
+
+        Ok("Successfully updated the file(s) and synchronized the local repository with the remote repository".to_string())
     }
 }
@@ -0,0 +1,297 @@
+use super::Command;
+use crate::error::{CommandError, ExecutionError};
+use crate::git::GitCommandExecuterBuilder;
+use crate::manifest::Manifest;
+use crate::message::MessageArgs;
+use crate::permissions;
+use crate::state::State;
+use crate::utils;
+use clap::Args;
+use colored::Colorize;
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("You need to set a remote repository before use dfmn")]
+    SetRemoteRepository,
+    #[error("There are no files to watch")]
+    NothingToWatch,
+}
+
+impl From<Error> for CommandError {
+    fn from(err: Error) -> Self {
+        CommandError::Usage(err.to_string())
+    }
+}
+
+/// Watches the tracked files and commits their changes automatically
+#[derive(Debug, Args)]
+pub struct Watch {
+    /// Milliseconds without changes to wait before committing
+    #[arg(long, default_value_t = 2000)]
+    debounce: u64,
+    /// Seconds between each push to the remote repository
+    #[arg(long, default_value_t = 300)]
+    push_interval: u64,
+}
+
+/// Copies the changed files to the repository, returning the names of the ones that differed
+fn copy_changed_files(
+    git_storage_folder_path: &Path,
+    manifest: &mut Manifest,
+    destinations: &HashMap<PathBuf, String>,
+    changed: &BTreeSet<PathBuf>,
+) -> Result<Vec<String>, ExecutionError> {
+    let mut copied = Vec::new();
+
+    for destination in changed {
+        let Some(name) = destinations.get(destination) else {
+            continue;
+        };
+
+        if !destination.is_file() {
+            continue;
+        }
+
+        let stored_file_path = git_storage_folder_path.join(name);
+
+        let two_files_are_equal =
+            match utils::check_if_files_are_equal(destination, &stored_file_path) {
+                Ok(result) => result,
+                Err(err) => {
+                    return Err(ExecutionError::Unknown {
+                        err: err.to_string(),
+                        trying_to: "check if files are equal",
+                    });
+                }
+            };
+
+        if two_files_are_equal {
+            continue;
+        }
+
+        if let Err(err) = fs::copy(destination, stored_file_path) {
+            return Err(ExecutionError::CopyFile(err.to_string()));
+        }
+
+        permissions::record(
+            manifest.files.entry(name.clone()).or_default(),
+            destination,
+            false,
+        )?;
+
+        copied.push(name.clone());
+    }
+
+    Ok(copied)
+}
+
+/// Copies the changed files to the repository and commits them, returning the
+/// names of the committed files
+fn commit_changes(
+    git_storage_folder_path: &Path,
+    destinations: &HashMap<PathBuf, String>,
+    changed: &BTreeSet<PathBuf>,
+) -> Result<Vec<String>, CommandError> {
+    let mut manifest = Manifest::load(git_storage_folder_path)?;
+    let copied = copy_changed_files(
+        git_storage_folder_path,
+        &mut manifest,
+        destinations,
+        changed,
+    )?;
+
+    if copied.is_empty() {
+        return Ok(copied);
+    }
+
+    manifest.save(git_storage_folder_path)?;
+
+    GitCommandExecuterBuilder::new(git_storage_folder_path)
+        .run_commit(MessageArgs::default().build("Update", &copied)?)
+        .offline()
+        .build()
+        .run()?;
+
+    let mut state = State::load()?;
+
+    for name in &copied {
+        state.record(
+            git_storage_folder_path,
+            name,
+            &git_storage_folder_path.join(name),
+        )?;
+    }
+
+    state.save()?;
+
+    Ok(copied)
+}
+
+fn push(git_storage_folder_path: &Path) -> bool {
+    if online::check(None).is_err() {
+        eprintln!(
+            "{}",
+            "No internet connection, the push was postponed".yellow()
+        );
+
+        return false;
+    }
+
+    if let Err(err) = GitCommandExecuterBuilder::new(git_storage_folder_path)
+        .run_push()
+        .build()
+        .run()
+    {
+        eprintln!("{}", "Error while trying to push".red());
+
+        log::error!("{err}");
+
+        return false;
+    }
+
+    println!("{}", "Pushed the changes to the remote repository".green());
+
+    true
+}
+
+impl Command for Watch {
+    fn execute(self) -> Result<String, CommandError> {
+        let git_storage_folder_path = match utils::get_git_storage_folder_path() {
+            Ok(path) => path,
+            Err(err) => {
+                return Err(ExecutionError::GetStorageFolderPath(err.to_string()).into());
+            }
+        };
+
+        let git_storage_folder_path = match git_storage_folder_path.canonicalize() {
+            Ok(path) => path,
+            Err(err) => {
+                return Err(ExecutionError::CanonicalizePath(err.to_string()).into());
+            }
+        };
+
+        if utils::check_if_remote_link_is_added().is_err() {
+            return Err(Error::SetRemoteRepository.into());
+        }
+
+        let manifest = Manifest::load(&git_storage_folder_path)?;
+        let mut destinations = HashMap::new();
+
+        for name in utils::get_tracked_files(&git_storage_folder_path)? {
+            destinations.insert(manifest.destination(&name)?, name);
+        }
+
+        if destinations.is_empty() {
+            return Err(Error::NothingToWatch.into());
+        }
+
+        let (sender, receiver) = mpsc::channel();
+
+        let mut watcher = match notify::recommended_watcher(sender) {
+            Ok(watcher) => watcher,
+            Err(err) => {
+                return Err(ExecutionError::Watch(err.to_string()).into());
+            }
+        };
+
+        // Editors usually replace the file when saving, so the folders are watched instead
+        let folders: HashSet<&Path> = destinations
+            .keys()
+            .filter_map(|path| path.parent())
+            .collect();
+
+        for folder in folders {
+            if !folder.is_dir() {
+                eprintln!(
+                    "{} {}",
+                    "Skipping the missing folder".yellow(),
+                    folder.display()
+                );
+
+                continue;
+            }
+
+            if let Err(err) = watcher.watch(folder, RecursiveMode::NonRecursive) {
+                return Err(ExecutionError::Watch(err.to_string()).into());
+            }
+        }
+
+        println!(
+            "{}",
+            format!(
+                "Watching {} file(s), press Ctrl-C to stop",
+                destinations.len()
+            )
+            .cyan()
+        );
+
+        let debounce = Duration::from_millis(self.debounce);
+        let push_interval = Duration::from_secs(self.push_interval);
+        let mut changed = BTreeSet::new();
+        let mut last_change = Instant::now();
+        let mut last_push = Instant::now();
+        let mut has_unpushed_commits = false;
+
+        loop {
+            match receiver.recv_timeout(debounce) {
+                Ok(Ok(event)) => {
+                    if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                        let tracked_paths: Vec<PathBuf> = event
+                            .paths
+                            .into_iter()
+                            .filter(|path| destinations.contains_key(path))
+                            .collect();
+
+                        // The untracked files of the watched folders, like the
+                        // shell history, would otherwise postpone the commit forever
+                        if !tracked_paths.is_empty() {
+                            changed.extend(tracked_paths);
+                            last_change = Instant::now();
+                        }
+                    }
+                }
+                Ok(Err(err)) => {
+                    return Err(ExecutionError::Watch(err.to_string()).into());
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(ExecutionError::Watch("watcher disconnected".to_string()).into());
+                }
+            }
+
+            if !changed.is_empty() && last_change.elapsed() >= debounce {
+                let result = commit_changes(&git_storage_folder_path, &destinations, &changed);
+
+                changed.clear();
+
+                // A failed commit is retried with the next change instead of
+                // stopping the watcher
+                match result {
+                    Ok(copied) if copied.is_empty() => {}
+                    Ok(copied) => {
+                        println!("{} {}", "Committed".green(), copied.join(", "));
+
+                        has_unpushed_commits = true;
+                    }
+                    Err(err) => {
+                        eprintln!("{}", "Error while trying to commit the changes".red());
+
+                        log::error!("{err}");
+                    }
+                }
+            }
+
+            if has_unpushed_commits && last_push.elapsed() >= push_interval {
+                last_push = Instant::now();
+                has_unpushed_commits = !push(&git_storage_folder_path);
+            }
+        }
+    }
+}
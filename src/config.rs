@@ -0,0 +1,103 @@
+use crate::error::ExecutionError;
+use crate::utils;
+use serde::Deserialize;
+use std::fs;
+
+pub const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// Settings of this machine, stored in the dfmn folder
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+    #[serde(default)]
+    pub commit: CommitConfig,
+    #[serde(default)]
+    pub log: LogConfig,
+    #[serde(default)]
+    pub merge: MergeConfig,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CommitConfig {
+    /// Template of the generated commit messages, accepts the {action}, {file},
+    /// {host}, {user} and {date} placeholders
+    #[serde(default = "default_template")]
+    pub template: String,
+    /// Appends the Dfmn-Host and Dfmn-User trailers to the commit messages
+    #[serde(default = "default_trailers")]
+    pub trailers: bool,
+}
+
+fn default_template() -> String {
+    "{action} {file}".to_string()
+}
+
+fn default_trailers() -> bool {
+    true
+}
+
+impl Default for CommitConfig {
+    fn default() -> Self {
+        CommitConfig {
+            template: default_template(),
+            trailers: default_trailers(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LogConfig {
+    /// Size in KiB after which the log file is rotated
+    #[serde(default = "default_max_size")]
+    pub max_size: u64,
+    /// Number of rotated log files that are kept
+    #[serde(default = "default_max_files")]
+    pub max_files: usize,
+}
+
+fn default_max_size() -> u64 {
+    1024
+}
+
+fn default_max_files() -> usize {
+    3
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        LogConfig {
+            max_size: default_max_size(),
+            max_files: default_max_files(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct MergeConfig {
+    /// Tool that merges the files, vimdiff, nvimdiff, meld or a command with
+    /// the {base}, {live}, {repository} and {merged} placeholders. git
+    /// merge-file and the editor are used when it is not set
+    #[serde(default)]
+    pub tool: Option<String>,
+}
+
+impl Config {
+    pub fn load() -> Result<Self, ExecutionError> {
+        let config_path = utils::get_dfm_folder_path()?.join(CONFIG_FILE_NAME);
+
+        if !config_path.exists() {
+            return Ok(Config::default());
+        }
+
+        let content = match fs::read_to_string(config_path) {
+            Ok(content) => content,
+            Err(err) => {
+                return Err(ExecutionError::ReadFile(err.to_string()));
+            }
+        };
+
+        match toml::from_str(&content) {
+            Ok(config) => Ok(config),
+            Err(err) => Err(ExecutionError::ParseConfig(err.to_string())),
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use crate::error::{CommandError, ExecutionError};
+use crate::hooks::{self, Event, Stage};
+use crate::manifest::Manifest;
+use crate::permissions;
+use crate::state;
+use crate::utils;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+pub enum Resolution {
+    Overwrite,
+    Backup,
+    Skip,
+}
+
+pub enum Outcome {
+    Deployed,
+    UpToDate,
+    BackedUp(PathBuf),
+    Skipped,
+}
+
+/// Copies a file from the repository to its destination, creating the missing
+/// folders. The destination gets the given mode, or the one of the source,
+/// before the content is written
+pub fn deploy_file(
+    source: &Path,
+    destination: &Path,
+    mode: Option<u32>,
+) -> Result<(), ExecutionError> {
+    if let Some(parent) = destination.parent() {
+        if let Err(err) = fs::create_dir_all(parent) {
+            return Err(ExecutionError::CreateFolder(err.to_string()));
+        }
+    }
+
+    permissions::copy_file(source, destination, mode)
+}
+
+fn show_diff(source: &Path, destination: &Path) -> Result<(), ExecutionError> {
+    if let Err(err) = Command::new("git")
+        .args(["diff", "--no-index", "--"])
+        .arg(destination)
+        .arg(source)
+        .status()
+    {
+        return Err(ExecutionError::GitCommand {
+            command: "diff",
+            err: err.to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Asks the user what to do with a file that already exists and differs from the repository version
+pub fn review_conflict(source: &Path, destination: &Path) -> Result<Resolution, ExecutionError> {
+    loop {
+        let Some(answer) = utils::prompt(&format!(
+            "{} already exists and differs from the repository version. [o]verwrite, [b]ackup and overwrite, [s]kip or show [d]iff?",
+            destination.display()
+        ))?
+        else {
+            // Without anybody to answer the live file is kept
+            return Ok(Resolution::Skip);
+        };
+
+        match answer.as_str() {
+            "o" | "overwrite" => return Ok(Resolution::Overwrite),
+            "b" | "backup" => return Ok(Resolution::Backup),
+            "s" | "skip" => return Ok(Resolution::Skip),
+            "d" | "diff" => show_diff(source, destination)?,
+            _ => continue,
+        }
+    }
+}
+
+/// Deploys a file, asking the user what to do when the destination already has a different version
+pub fn deploy_with_review(
+    source: &Path,
+    destination: &Path,
+    mode: Option<u32>,
+    force: bool,
+) -> Result<Outcome, ExecutionError> {
+    if !destination.exists() {
+        deploy_file(source, destination, mode)?;
+
+        return Ok(Outcome::Deployed);
+    }
+
+    let two_files_are_equal = match utils::check_if_files_are_equal(source, destination) {
+        Ok(result) => result,
+        Err(err) => {
+            return Err(ExecutionError::Unknown {
+                err: err.to_string(),
+                trying_to: "check if files are equal",
+            });
+        }
+    };
+
+    if two_files_are_equal {
+        return Ok(Outcome::UpToDate);
+    }
+
+    let resolution = if force {
+        Resolution::Overwrite
+    } else {
+        review_conflict(source, destination)?
+    };
+
+    match resolution {
+        Resolution::Overwrite => {
+            deploy_file(source, destination, mode)?;
+
+            Ok(Outcome::Deployed)
+        }
+        Resolution::Backup => {
+            let mut backup_path = destination.as_os_str().to_owned();
+            backup_path.push(".dfmn-backup");
+            let backup_path = PathBuf::from(backup_path);
+
+            if let Err(err) = fs::rename(destination, &backup_path) {
+                return Err(ExecutionError::RenameFile(err.to_string()));
+            }
+
+            deploy_file(source, destination, mode)?;
+
+            Ok(Outcome::BackedUp(backup_path))
+        }
+        Resolution::Skip => Ok(Outcome::Skipped),
+    }
+}
+
+/// Deploys a tracked file to its destination, running its deploy hooks and
+/// restoring its permissions
+pub fn deploy_tracked_file(
+    git_storage_folder_path: &Path,
+    manifest: &Manifest,
+    name: &str,
+    force: bool,
+    no_hooks: bool,
+) -> Result<Outcome, CommandError> {
+    let destination = manifest.destination(name)?;
+
+    if !no_hooks {
+        hooks::run_for_file(manifest, name, &destination, Stage::Pre, Event::Deploy)?;
+    }
+
+    let stored_file_path = git_storage_folder_path.join(name);
+    let entry = manifest.files.get(name);
+
+    permissions::restore_mode(entry, &stored_file_path)?;
+
+    let outcome = deploy_with_review(
+        &stored_file_path,
+        &destination,
+        permissions::get_recorded_mode(entry),
+        force,
+    )?;
+
+    if let Outcome::Deployed | Outcome::BackedUp(_) | Outcome::UpToDate = outcome {
+        state::record(git_storage_folder_path, name, &destination)?;
+    }
+
+    if let Outcome::Deployed | Outcome::BackedUp(_) = outcome {
+        permissions::restore(entry, &destination)?;
+
+        if !no_hooks {
+            hooks::run_for_file(manifest, name, &destination, Stage::Post, Event::Deploy)?;
+        }
+    }
+
+    Ok(outcome)
+}
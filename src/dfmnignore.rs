@@ -0,0 +1,36 @@
+use crate::error::ExecutionError;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::path::Path;
+
+pub const IGNORE_FILE_NAME: &str = ".dfmnignore";
+
+/// Matches the repository paths against the patterns of the `.dfmnignore` file,
+/// which uses the gitignore syntax
+pub struct IgnoreMatcher {
+    gitignore: Gitignore,
+}
+
+impl IgnoreMatcher {
+    pub fn load(git_storage_folder_path: &Path) -> Result<Self, ExecutionError> {
+        let mut builder = GitignoreBuilder::new(git_storage_folder_path);
+        let ignore_file_path = git_storage_folder_path.join(IGNORE_FILE_NAME);
+
+        if ignore_file_path.exists() {
+            if let Some(err) = builder.add(ignore_file_path) {
+                return Err(ExecutionError::ParseIgnoreFile(err.to_string()));
+            }
+        }
+
+        match builder.build() {
+            Ok(gitignore) => Ok(IgnoreMatcher { gitignore }),
+            Err(err) => Err(ExecutionError::ParseIgnoreFile(err.to_string())),
+        }
+    }
+
+    /// Checks if a path relative to the repository root, or one of its parents, is ignored
+    pub fn is_ignored(&self, name: &str, is_dir: bool) -> bool {
+        self.gitignore
+            .matched_path_or_any_parents(name, is_dir)
+            .is_ignore()
+    }
+}
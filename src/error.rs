@@ -30,14 +30,60 @@ pub enum ExecutionError {
     RemoveStorageFolder(String),
     #[error("Error while trying create the storage folder: {0}")]
     CreateStorageFolder(String),
+    #[error("Error while trying to create a folder: {0}")]
+    CreateFolder(String),
+    #[error("Error while trying to rename a file: {0}")]
+    RenameFile(String),
+    #[error("Error while trying to read the user input: {0}")]
+    ReadInput(String),
+    #[error("Error while trying to parse the manifest: {0}")]
+    ParseManifest(String),
+    #[error("Error while trying to serialize the manifest: {0}")]
+    SerializeManifest(String),
+    #[error("Error while trying to parse the ignore file: {0}")]
+    ParseIgnoreFile(String),
+    #[error("Error while trying to execute the hook {command:?}: {err:?}")]
+    Hook { command: String, err: String },
+    #[error("Error while trying to watch the files: {0}")]
+    Watch(String),
+    #[error("Error while trying to get the metadata of a file: {0}")]
+    GetMetadata(String),
+    #[error("Error while trying to set the permissions of a file: {0}")]
+    SetPermissions(String),
+    #[error("Error while trying to set the modification time of a file: {0}")]
+    SetModificationTime(String),
+    #[error("Error while trying to parse the config file: {0}")]
+    ParseConfig(String),
+    #[error("Error while trying to parse the state file: {0}")]
+    ParseState(String),
+    #[error("Error while trying to serialize the state file: {0}")]
+    SerializeState(String),
+    #[error("Error while trying to open the editor: {0}")]
+    OpenEditor(String),
+    #[error("Error while trying to open the pager: {0}")]
+    OpenPager(String),
+    #[error("Error while trying to open the merge tool: {0}")]
+    OpenMergeTool(String),
+    #[error("Error while trying to draw the terminal UI: {0}")]
+    Terminal(String),
     #[error("Invalid UTF-8 while trying to: {0}")]
     InvalidUTF8(&'static str),
     #[error("Error while trying to create the ssh regex: {0}")]
     Regex(String),
-    #[error("Error while trying to execute {0} non zero status code")]
-    NoSuccess(&'static str),
+    #[error("Error while trying to execute {command}, non zero status code: {output}")]
+    NoSuccess {
+        command: &'static str,
+        output: String,
+    },
+    #[error("{command} conflicts with the remote repository: {output}")]
+    Conflict {
+        command: &'static str,
+        output: String,
+    },
     #[error("Repository not found")]
     RepositoryNotFound,
+    #[error("The destination {0:?} of dfmn.toml is outside your home folder")]
+    DestinationOutsideHome(String),
     #[error("Something wrong happened: {err:?}, while trying to: {trying_to:?}")]
     Unknown {
         err: String,
@@ -50,11 +96,50 @@ pub enum CommandError {
     #[error("{0}")]
     Usage(String),
     #[error("{0}")]
+    Network(String),
+    #[error("{0}")]
+    Git(String),
+    #[error("{0}")]
+    Conflict(String),
+    #[error("{0}")]
     Execution(ExecutionError),
 }
 
+/// Exit code of the internal errors, such as failing to read or write a file
+pub const EXIT_INTERNAL: u8 = 1;
+/// Exit code of the invalid arguments and the operations that are not allowed
+pub const EXIT_USAGE: u8 = 2;
+/// Exit code when there is no internet connection
+pub const EXIT_NETWORK: u8 = 3;
+/// Exit code when git fails or can not be executed
+pub const EXIT_GIT: u8 = 4;
+/// Exit code when the local repository conflicts with the remote repository
+pub const EXIT_CONFLICT: u8 = 5;
+
 impl From<ExecutionError> for CommandError {
     fn from(err: ExecutionError) -> Self {
-        CommandError::Execution(err)
+        match err {
+            ExecutionError::Conflict { .. } => CommandError::Conflict(err.to_string()),
+            ExecutionError::DestinationOutsideHome(_) => CommandError::Usage(err.to_string()),
+            err => CommandError::Execution(err),
+        }
+    }
+}
+
+impl CommandError {
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            CommandError::Usage(_) => EXIT_USAGE,
+            CommandError::Network(_) => EXIT_NETWORK,
+            CommandError::Git(_) => EXIT_GIT,
+            CommandError::Conflict(_) => EXIT_CONFLICT,
+            CommandError::Execution(
+                ExecutionError::GitCommand { .. }
+                | ExecutionError::NoSuccess { .. }
+                | ExecutionError::RepositoryNotFound,
+            ) => EXIT_GIT,
+            CommandError::Execution(ExecutionError::Conflict { .. }) => EXIT_CONFLICT,
+            CommandError::Execution(_) => EXIT_INTERNAL,
+        }
     }
 }
@@ -1,4 +1,9 @@
+use crate::dfmnignore::IGNORE_FILE_NAME;
 use crate::error::ExecutionError;
+use crate::logger;
+use std::collections::HashMap;
+use std::env;
+use std::ffi::{OsStr, OsString};
 use std::path::Path;
 use std::process::{Command, Output, Stdio};
 
@@ -9,8 +14,11 @@ pub struct GitCommandExecuter<'a> {
     run_init: bool,
     run_remote_add: bool,
     run_remote_remove: bool,
+    run_fetch: bool,
     run_pull: bool,
     run_commit: bool,
+    run_push: bool,
+    offline: bool,
 }
 
 pub struct GitCommandExecuterBuilder<'a> {
@@ -20,8 +28,11 @@ pub struct GitCommandExecuterBuilder<'a> {
     run_init: bool,
     run_remote_add: bool,
     run_remote_remove: bool,
+    run_fetch: bool,
     run_pull: bool,
     run_commit: bool,
+    run_push: bool,
+    offline: bool,
 }
 
 struct GitError {
@@ -40,27 +51,41 @@ impl From<GitError> for ExecutionError {
 
 impl GitCommandExecuter<'_> {
     pub fn run(self) -> Result<(), ExecutionError> {
-        if self.run_init && !init(self.git_storage_folder_path)?.status.success() {
-            return Err(ExecutionError::NoSuccess("git init"));
+        if self.run_init {
+            let output = init(self.git_storage_folder_path)?;
+
+            if !output.status.success() {
+                return Err(no_success("git init", &output));
+            }
         }
 
         if self.run_remote_remove {
-            if !remote_remove(self.git_storage_folder_path)?
-                .status
-                .success()
-            {
-                return Err(ExecutionError::NoSuccess("git remote remove"));
+            let output = remote_remove(self.git_storage_folder_path)?;
+
+            if !output.status.success() {
+                return Err(no_success("git remote remove", &output));
             }
 
             return Ok(());
         }
 
-        if self.run_remote_add
-            && !remote_add(self.git_storage_folder_path, &self.remote_link)?
-                .status
-                .success()
-        {
-            return Err(ExecutionError::NoSuccess("git remote add"));
+        if self.run_remote_add {
+            let output = remote_add(self.git_storage_folder_path, &self.remote_link)?;
+
+            if !output.status.success() {
+                return Err(no_success("git remote add", &output));
+            }
+        }
+
+        if self.run_fetch {
+            let output = fetch(self.git_storage_folder_path)?;
+
+            if !output.status.success()
+                && !String::from_utf8_lossy(&output.stderr)
+                    .contains("couldn't find remote ref main")
+            {
+                return Err(no_success("git fetch", &output));
+            }
         }
 
         if self.run_pull && self.run_remote_add {
@@ -70,48 +95,40 @@ impl GitCommandExecuter<'_> {
                 return Ok(());
             }
 
-            let output = pull(self.git_storage_folder_path)?.stderr;
-
-            let Ok(output) = String::from_utf8(output) else {
-                return Err(ExecutionError::InvalidUTF8("Convert Vec<u8> to String"));
-            };
-
-            if output.contains("Repository not found") {
+            if String::from_utf8_lossy(&output.stderr).contains("Repository not found") {
                 return Err(ExecutionError::RepositoryNotFound);
             }
-        } else if self.run_pull || self.run_commit {
+        } else if self.run_pull || (self.run_commit && !self.offline) {
             let output = pull(self.git_storage_folder_path)?;
 
-            if !output.status.success() {
-                let output = pull(self.git_storage_folder_path)?.stderr;
+            if !output.status.success()
+                && !String::from_utf8_lossy(&output.stderr)
+                    .contains("couldn't find remote ref main")
+            {
+                return Err(no_success("git pull", &output));
+            }
+        }
 
-                let Ok(output) = String::from_utf8(output) else {
-                    return Err(ExecutionError::InvalidUTF8("Convert Vec<u8> to String"));
-                };
+        if self.run_commit {
+            let output = add_all(self.git_storage_folder_path)?;
 
-                if !output.contains("couldn't find remote ref main") {
-                    return Err(ExecutionError::NoSuccess("git pull"));
-                }
+            if !output.status.success() {
+                return Err(no_success("git add", &output));
             }
-        }
 
-        if !self.run_commit {
-            return Ok(());
-        }
+            let output = commit(self.git_storage_folder_path, &self.commit_message)?;
 
-        if !add_all(self.git_storage_folder_path)?.status.success() {
-            return Err(ExecutionError::NoSuccess("git add"));
+            if !output.status.success() {
+                return Err(no_success("git commit", &output));
+            }
         }
 
-        if !commit(self.git_storage_folder_path, &self.commit_message)?
-            .status
-            .success()
-        {
-            return Err(ExecutionError::NoSuccess("git commit"));
-        }
+        if self.run_push || (self.run_commit && !self.offline) {
+            let output = push(self.git_storage_folder_path)?;
 
-        if !push(self.git_storage_folder_path)?.status.success() {
-            return Err(ExecutionError::NoSuccess("git push"));
+            if !output.status.success() {
+                return Err(no_success("git push", &output));
+            }
         }
 
         Ok(())
@@ -127,8 +144,11 @@ impl<'a> GitCommandExecuterBuilder<'a> {
             remote_link: String::new(),
             run_commit: false,
             run_remote_add: false,
+            run_fetch: false,
             run_pull: false,
             run_remote_remove: false,
+            run_push: false,
+            offline: false,
         }
     }
 
@@ -145,12 +165,32 @@ impl<'a> GitCommandExecuterBuilder<'a> {
         self
     }
 
+    /// Downloads the commits of the remote repository without merging them
+    pub fn run_fetch(mut self) -> Self {
+        self.run_fetch = true;
+
+        self
+    }
+
     pub fn run_pull(mut self) -> Self {
         self.run_pull = true;
 
         self
     }
 
+    pub fn run_push(mut self) -> Self {
+        self.run_push = true;
+
+        self
+    }
+
+    /// Commits without pulling from or pushing to the remote repository
+    pub fn offline(mut self) -> Self {
+        self.offline = true;
+
+        self
+    }
+
     pub fn run_remote_remove(mut self) -> Self {
         self.run_remote_remove = true;
 
@@ -172,135 +212,541 @@ impl<'a> GitCommandExecuterBuilder<'a> {
             remote_link: self.remote_link,
             run_commit: self.run_commit,
             run_remote_add: self.run_remote_add,
+            run_fetch: self.run_fetch,
             run_pull: self.run_pull,
             run_remote_remove: self.run_remote_remove,
+            run_push: self.run_push,
+            offline: self.offline,
         }
     }
 }
 
-fn init(git_storage_folder_path: &Path) -> Result<Output, GitError> {
-    let output = match Command::new("git")
-        .arg("init")
-        .current_dir(git_storage_folder_path)
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .output()
-    {
-        Ok(output) => output,
-        Err(err) => {
-            return Err(GitError {
-                command: "init",
-                err: err.to_string(),
-            });
-        }
+/// Returns the current commit of the repository or `None` if it has no commits yet
+pub fn get_head(git_storage_folder_path: &Path) -> Result<Option<String>, ExecutionError> {
+    let output = rev_parse_head(git_storage_folder_path)?;
+
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    let Ok(output) = String::from_utf8(output.stdout) else {
+        return Err(ExecutionError::InvalidUTF8("Convert Vec<u8> to String"));
     };
 
-    Ok(output)
+    Ok(Some(output.trim().to_string()))
 }
 
-fn add_all(git_storage_folder_path: &Path) -> Result<Output, GitError> {
-    let output = match Command::new("git")
-        .args(["add", "."])
-        .current_dir(git_storage_folder_path)
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .output()
-    {
-        Ok(output) => output,
-        Err(err) => {
-            return Err(GitError {
-                command: "add",
-                err: err.to_string(),
-            });
-        }
+/// Returns the files that changed between two commits. If there is no starting
+/// commit every file of the final commit is returned
+pub fn get_changed_files(
+    git_storage_folder_path: &Path,
+    from: Option<&str>,
+    to: &str,
+) -> Result<Vec<String>, ExecutionError> {
+    let output = match from {
+        Some(from) => diff_name_only(git_storage_folder_path, from, to)?,
+        None => ls_tree(git_storage_folder_path, to)?,
     };
 
-    Ok(output)
-}
+    if !output.status.success() {
+        return Err(no_success("git diff", &output));
+    }
 
-fn commit(git_storage_folder_path: &Path, commit_name: &str) -> Result<Output, GitError> {
-    let output = match Command::new("git")
-        .args(["commit", "-m", commit_name])
-        .current_dir(git_storage_folder_path)
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .output()
-    {
-        Ok(output) => output,
-        Err(err) => {
-            return Err(GitError {
-                command: "commit",
-                err: err.to_string(),
-            });
-        }
+    let Ok(output) = String::from_utf8(output.stdout) else {
+        return Err(ExecutionError::InvalidUTF8("Convert Vec<u8> to String"));
     };
 
-    Ok(output)
+    Ok(output.lines().map(str::to_string).collect())
 }
 
-fn push(git_storage_folder_path: &Path) -> Result<Output, GitError> {
-    let output = match Command::new("git")
-        .args(["push", "origin", "main"])
-        .current_dir(git_storage_folder_path)
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .output()
-    {
-        Ok(output) => output,
-        Err(err) => {
-            return Err(GitError {
-                command: "push",
-                err: err.to_string(),
-            });
+pub fn get_version() -> Result<String, ExecutionError> {
+    let output = capture(Path::new("."), &["--version"], "--version")?;
+
+    if !output.status.success() {
+        return Err(no_success("git --version", &output));
+    }
+
+    output_to_string(output.stdout)
+}
+
+/// Checks if the folder is the root of a git repository
+pub fn is_repository_root(git_storage_folder_path: &Path) -> Result<bool, ExecutionError> {
+    let output = capture(
+        git_storage_folder_path,
+        &["rev-parse", "--show-toplevel"],
+        "rev-parse",
+    )?;
+
+    if !output.status.success() {
+        return Ok(false);
+    }
+
+    let top_level = output_to_string(output.stdout)?;
+
+    Ok(Path::new(&top_level).canonicalize().ok() == git_storage_folder_path.canonicalize().ok())
+}
+
+pub fn get_remote_url(git_storage_folder_path: &Path) -> Result<Option<String>, ExecutionError> {
+    let output = capture(
+        git_storage_folder_path,
+        &["remote", "get-url", "origin"],
+        "remote get-url",
+    )?;
+
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    Ok(Some(output_to_string(output.stdout)?))
+}
+
+/// Lists the branches of the remote repository, failing with the git error
+/// message when it can not be reached without asking for a password
+pub fn list_remote_branches(
+    git_storage_folder_path: &Path,
+) -> Result<Result<Vec<String>, String>, ExecutionError> {
+    let ssh_command = env::var("GIT_SSH_COMMAND")
+        .unwrap_or_else(|_| "ssh -o BatchMode=yes -o ConnectTimeout=10".to_string());
+
+    let output = capture_with_env(
+        git_storage_folder_path,
+        &["ls-remote", "--heads", "origin"],
+        &[
+            ("GIT_SSH_COMMAND", &ssh_command),
+            ("GIT_TERMINAL_PROMPT", "0"),
+        ],
+        "ls-remote",
+    )?;
+
+    if !output.status.success() {
+        return Ok(Err(output_to_string(output.stderr)?));
+    }
+
+    Ok(Ok(output_to_string(output.stdout)?
+        .lines()
+        .filter_map(|line| line.split("refs/heads/").nth(1))
+        .map(str::to_string)
+        .collect()))
+}
+
+pub fn branch_exists(git_storage_folder_path: &Path, branch: &str) -> Result<bool, ExecutionError> {
+    let output = capture(
+        git_storage_folder_path,
+        &["rev-parse", "--verify", "--quiet", branch],
+        "rev-parse",
+    )?;
+
+    Ok(output.status.success())
+}
+
+/// Returns whether a revision, like a commit hash, a branch or `HEAD~2`, names
+/// a commit of the repository
+pub fn revision_exists(
+    git_storage_folder_path: &Path,
+    revision: &str,
+) -> Result<bool, ExecutionError> {
+    let output = capture(
+        git_storage_folder_path,
+        &[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{revision}^{{commit}}"),
+        ],
+        "rev-parse",
+    )?;
+
+    Ok(output.status.success())
+}
+
+/// Returns the content of a file at a revision or `None` if the file was not
+/// in the repository at that revision
+pub fn get_file_at(
+    git_storage_folder_path: &Path,
+    revision: &str,
+    name: &str,
+) -> Result<Option<Vec<u8>>, ExecutionError> {
+    get_blob(git_storage_folder_path, &format!("{revision}:{name}"))
+}
+
+/// Returns the content of a blob, named by its hash or by `<revision>:<name>`,
+/// or `None` if the repository does not have it
+pub fn get_blob(
+    git_storage_folder_path: &Path,
+    object: &str,
+) -> Result<Option<Vec<u8>>, ExecutionError> {
+    let output = capture(
+        git_storage_folder_path,
+        &["cat-file", "-e", object],
+        "cat-file",
+    )?;
+
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    let output = capture(
+        git_storage_folder_path,
+        &["cat-file", "blob", object],
+        "cat-file",
+    )?;
+
+    if !output.status.success() {
+        return Err(no_success("git cat-file", &output));
+    }
+
+    Ok(Some(output.stdout))
+}
+
+/// Merges the changes from `base` to `repository` into `live`, writing the
+/// conflict markers in it. Returns whether the merge had no conflicts
+pub fn merge_file(
+    git_storage_folder_path: &Path,
+    live: &Path,
+    base: &Path,
+    repository: &Path,
+) -> Result<bool, ExecutionError> {
+    let output = capture(
+        git_storage_folder_path,
+        &[
+            OsStr::new("merge-file"),
+            OsStr::new("-L"),
+            OsStr::new("live"),
+            OsStr::new("-L"),
+            OsStr::new("base"),
+            OsStr::new("-L"),
+            OsStr::new("repository"),
+            live.as_os_str(),
+            base.as_os_str(),
+            repository.as_os_str(),
+        ],
+        "merge-file",
+    )?;
+
+    // git merge-file exits with the number of conflicts, or a negative
+    // number when it fails
+    match output.status.code() {
+        Some(0) => Ok(true),
+        Some(1..=127) => Ok(false),
+        _ => Err(no_success("git merge-file", &output)),
+    }
+}
+
+/// Returns the files changed by the fetched commits of the remote repository
+/// that are not merged yet, with their status letter (`A`, `M` or `D`)
+pub fn get_incoming_changes(
+    git_storage_folder_path: &Path,
+) -> Result<Vec<(char, String)>, ExecutionError> {
+    if !revision_exists(git_storage_folder_path, "origin/main")? {
+        return Ok(Vec::new());
+    }
+
+    if get_head(git_storage_folder_path)?.is_none() {
+        return Ok(
+            get_changed_files(git_storage_folder_path, None, "origin/main")?
+                .into_iter()
+                .map(|name| ('A', name))
+                .collect(),
+        );
+    }
+
+    let output = capture(
+        git_storage_folder_path,
+        &[
+            "diff",
+            "--name-status",
+            "--no-renames",
+            "HEAD...origin/main",
+        ],
+        "diff",
+    )?;
+
+    if !output.status.success() {
+        return Err(no_success("git diff", &output));
+    }
+
+    Ok(output_to_string(output.stdout)?
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .filter_map(|(status, name)| Some((status.chars().next()?, name.to_string())))
+        .collect())
+}
+
+/// Returns the hash that git gives to the content of a file, which can be in or
+/// out of the repository
+pub fn hash_file(git_storage_folder_path: &Path, path: &Path) -> Result<String, ExecutionError> {
+    let output = capture(
+        git_storage_folder_path,
+        &[
+            OsStr::new("hash-object"),
+            OsStr::new("--no-filters"),
+            OsStr::new("--"),
+            path.as_os_str(),
+        ],
+        "hash-object",
+    )?;
+
+    if !output.status.success() {
+        return Err(no_success("git hash-object", &output));
+    }
+
+    output_to_string(output.stdout)
+}
+
+/// Returns the hash of a file at a revision or `None` if the file was not in
+/// the repository at that revision
+pub fn get_blob_hash(
+    git_storage_folder_path: &Path,
+    revision: &str,
+    name: &str,
+) -> Result<Option<String>, ExecutionError> {
+    let output = capture(
+        git_storage_folder_path,
+        &[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{revision}:{name}"),
+        ],
+        "rev-parse",
+    )?;
+
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    Ok(Some(output_to_string(output.stdout)?))
+}
+
+/// The last commit that changed a file
+pub struct LastCommit {
+    pub timestamp: i64,
+    pub date: String,
+    pub subject: String,
+}
+
+/// Returns the last commit that changed every file of the repository
+pub fn get_last_commits(
+    git_storage_folder_path: &Path,
+) -> Result<HashMap<String, LastCommit>, ExecutionError> {
+    let output = capture(
+        git_storage_folder_path,
+        &[
+            "-c",
+            "core.quotePath=false",
+            "log",
+            "--format=%x00%ct%x09%cs%x09%s",
+            "--name-only",
+        ],
+        "log",
+    )?;
+
+    let mut last_commits = HashMap::new();
+
+    // A repository without commits has no log
+    if !output.status.success() {
+        return Ok(last_commits);
+    }
+
+    let output = output_to_string(output.stdout)?;
+    let mut commit = None;
+
+    for line in output.lines() {
+        if let Some(header) = line.strip_prefix('\0') {
+            let mut fields = header.splitn(3, '\t');
+
+            commit = Some((
+                fields
+                    .next()
+                    .and_then(|field| field.parse().ok())
+                    .unwrap_or_default(),
+                fields.next().unwrap_or_default().to_string(),
+                fields.next().unwrap_or_default().to_string(),
+            ));
+        } else if let Some((timestamp, date, subject)) =
+            commit.as_ref().filter(|_| !line.is_empty())
+        {
+            // The log goes from the newest commit, so the first one is kept
+            last_commits
+                .entry(line.to_string())
+                .or_insert_with(|| LastCommit {
+                    timestamp: *timestamp,
+                    date: date.clone(),
+                    subject: subject.clone(),
+                });
         }
+    }
+
+    Ok(last_commits)
+}
+
+/// Counts the local commits that are not in the remote branch, or returns
+/// `None` if the remote branch is unknown
+pub fn count_unpushed_commits(
+    git_storage_folder_path: &Path,
+) -> Result<Option<usize>, ExecutionError> {
+    let output = capture(
+        git_storage_folder_path,
+        &["rev-list", "--count", "origin/main..HEAD"],
+        "rev-list",
+    )?;
+
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    Ok(output_to_string(output.stdout)?.parse().ok())
+}
+
+/// Returns the files with uncommitted changes
+pub fn get_dirty_files(git_storage_folder_path: &Path) -> Result<Vec<String>, ExecutionError> {
+    let output = capture(
+        git_storage_folder_path,
+        &["status", "--porcelain"],
+        "status",
+    )?;
+
+    if !output.status.success() {
+        return Err(no_success("git status", &output));
+    }
+
+    // The status is not trimmed since its first column can be a space
+    let Ok(output) = String::from_utf8(output.stdout) else {
+        return Err(ExecutionError::InvalidUTF8("Convert Vec<u8> to String"));
     };
 
-    Ok(output)
+    Ok(output
+        .lines()
+        .filter_map(|line| line.get(3..))
+        .map(str::to_string)
+        .collect())
 }
 
-fn remote_remove(git_storage_folder_path: &Path) -> Result<Output, GitError> {
-    let output = match Command::new("git")
-        .args(["remote", "remove", "origin"])
-        .current_dir(git_storage_folder_path)
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .output()
-    {
-        Ok(output) => output,
-        Err(err) => {
-            return Err(GitError {
-                command: "remote remove",
-                err: err.to_string(),
-            });
-        }
+/// Renames a tracked file with git, so its history can be followed
+pub fn move_file(
+    git_storage_folder_path: &Path,
+    from: &str,
+    to: &str,
+) -> Result<(), ExecutionError> {
+    let output = capture(git_storage_folder_path, &["mv", "--", from, to], "mv")?;
+
+    if !output.status.success() {
+        return Err(no_success("git mv", &output));
+    }
+
+    Ok(())
+}
+
+/// Returns the diff between two files, which do not need to be in the repository
+pub fn diff_files(
+    git_storage_folder_path: &Path,
+    from: &Path,
+    to: &Path,
+) -> Result<String, ExecutionError> {
+    let output = capture(
+        git_storage_folder_path,
+        &[
+            OsStr::new("diff"),
+            OsStr::new("--no-index"),
+            OsStr::new("--no-color"),
+            OsStr::new("--"),
+            from.as_os_str(),
+            to.as_os_str(),
+        ],
+        "diff",
+    )?;
+
+    // git diff exits with 1 when the files differ
+    if !matches!(output.status.code(), Some(0 | 1)) {
+        return Err(no_success("git diff", &output));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn output_to_string(output: Vec<u8>) -> Result<String, ExecutionError> {
+    let Ok(output) = String::from_utf8(output) else {
+        return Err(ExecutionError::InvalidUTF8("Convert Vec<u8> to String"));
     };
 
-    Ok(output)
+    Ok(output.trim().to_string())
 }
 
-fn remote_add(git_storage_folder_path: &Path, link: &str) -> Result<Output, GitError> {
-    let output = match Command::new("git")
-        .args(["remote", "add", "origin", link])
-        .current_dir(git_storage_folder_path)
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .output()
+/// Builds the error of a git command that exited with a non zero status code,
+/// keeping what git printed and telling the conflicts with the remote apart
+fn no_success(command: &'static str, output: &Output) -> ExecutionError {
+    let output_text = [&output.stderr, &output.stdout]
+        .iter()
+        .map(|stream| String::from_utf8_lossy(stream).trim().to_string())
+        .filter(|stream| !stream.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let is_conflict = [
+        "CONFLICT",
+        "Automatic merge failed",
+        "divergent branches",
+        "[rejected]",
+    ]
+    .iter()
+    .any(|pattern| output_text.contains(pattern));
+
+    if is_conflict {
+        return ExecutionError::Conflict {
+            command,
+            output: output_text,
+        };
+    }
+
+    ExecutionError::NoSuccess {
+        command,
+        output: if output_text.is_empty() {
+            format!("no output, {}", output.status)
+        } else {
+            output_text
+        },
+    }
+}
+
+fn log_output(stream: &str, output: &[u8]) {
+    for line in String::from_utf8_lossy(output)
+        .lines()
+        .filter(|line| !line.is_empty())
     {
-        Ok(output) => output,
-        Err(err) => {
-            return Err(GitError {
-                command: "remote add",
-                err: err.to_string(),
-            });
-        }
-    };
+        log::debug!("git {stream}: {line}");
+    }
+}
 
-    Ok(output)
+fn capture<S: AsRef<OsStr>>(
+    git_storage_folder_path: &Path,
+    args: &[S],
+    command: &'static str,
+) -> Result<Output, GitError> {
+    capture_with_env(git_storage_folder_path, args, &[], command)
 }
 
-fn pull(git_storage_folder_path: &Path) -> Result<Output, GitError> {
+/// Runs a git command capturing its output, which is logged so it can be seen
+/// with the verbose flags
+fn capture_with_env<S: AsRef<OsStr>>(
+    git_storage_folder_path: &Path,
+    args: &[S],
+    envs: &[(&str, &str)],
+    command: &'static str,
+) -> Result<Output, GitError> {
+    let display_args: Vec<_> = args
+        .iter()
+        .map(|arg| logger::quote(arg.as_ref().to_string_lossy().to_string()))
+        .collect();
+
+    log::info!(
+        "Running git {} in {}",
+        display_args.join(" "),
+        git_storage_folder_path.display()
+    );
+
     let output = match Command::new("git")
-        .args(["pull", "origin", "main"])
+        .args(args)
+        .envs(envs.iter().copied())
         .current_dir(git_storage_folder_path)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -308,12 +754,111 @@ fn pull(git_storage_folder_path: &Path) -> Result<Output, GitError> {
     {
         Ok(output) => output,
         Err(err) => {
+            log::error!("Could not execute git {command}: {err}");
+
             return Err(GitError {
-                command: "git pull",
+                command,
                 err: err.to_string(),
             });
         }
     };
 
+    log_output("stdout", &output.stdout);
+    log_output("stderr", &output.stderr);
+    log::debug!("git {command} finished with {}", output.status);
+
     Ok(output)
 }
+
+fn init(git_storage_folder_path: &Path) -> Result<Output, GitError> {
+    capture(git_storage_folder_path, &["init"], "init")
+}
+
+fn add_all(git_storage_folder_path: &Path) -> Result<Output, GitError> {
+    let mut excludes_file = OsString::from("core.excludesFile=");
+    excludes_file.push(git_storage_folder_path.join(IGNORE_FILE_NAME));
+
+    capture(
+        git_storage_folder_path,
+        &[
+            OsStr::new("-c"),
+            &excludes_file,
+            OsStr::new("add"),
+            OsStr::new("."),
+        ],
+        "add",
+    )
+}
+
+fn commit(git_storage_folder_path: &Path, commit_name: &str) -> Result<Output, GitError> {
+    capture(
+        git_storage_folder_path,
+        &["commit", "-m", commit_name],
+        "commit",
+    )
+}
+
+fn push(git_storage_folder_path: &Path) -> Result<Output, GitError> {
+    capture(git_storage_folder_path, &["push", "origin", "main"], "push")
+}
+
+fn remote_remove(git_storage_folder_path: &Path) -> Result<Output, GitError> {
+    capture(
+        git_storage_folder_path,
+        &["remote", "remove", "origin"],
+        "remote remove",
+    )
+}
+
+fn remote_add(git_storage_folder_path: &Path, link: &str) -> Result<Output, GitError> {
+    capture(
+        git_storage_folder_path,
+        &["remote", "add", "origin", link],
+        "remote add",
+    )
+}
+
+fn fetch(git_storage_folder_path: &Path) -> Result<Output, GitError> {
+    capture(
+        git_storage_folder_path,
+        &["fetch", "origin", "main"],
+        "fetch",
+    )
+}
+
+fn pull(git_storage_folder_path: &Path) -> Result<Output, GitError> {
+    // The local commits made offline are merged with the remote ones
+    capture(
+        git_storage_folder_path,
+        &["pull", "--no-rebase", "--no-edit", "origin", "main"],
+        "pull",
+    )
+}
+
+fn rev_parse_head(git_storage_folder_path: &Path) -> Result<Output, GitError> {
+    capture(
+        git_storage_folder_path,
+        &["rev-parse", "--verify", "--quiet", "HEAD"],
+        "rev-parse",
+    )
+}
+
+fn diff_name_only(
+    git_storage_folder_path: &Path,
+    from: &str,
+    to: &str,
+) -> Result<Output, GitError> {
+    capture(
+        git_storage_folder_path,
+        &["diff", "--name-only", from, to],
+        "diff",
+    )
+}
+
+fn ls_tree(git_storage_folder_path: &Path, commit: &str) -> Result<Output, GitError> {
+    capture(
+        git_storage_folder_path,
+        &["ls-tree", "-r", "--name-only", commit],
+        "ls-tree",
+    )
+}
@@ -0,0 +1,209 @@
+use crate::error::{CommandError, ExecutionError};
+use crate::manifest::Manifest;
+use crate::utils;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+use std::process::{Command, Output};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("The {stage}-{event} hook {command:?} failed, aborting")]
+    PreHookFailed {
+        stage: Stage,
+        event: Event,
+        command: String,
+    },
+}
+
+impl From<Error> for CommandError {
+    fn from(err: Error) -> Self {
+        CommandError::Usage(err.to_string())
+    }
+}
+
+/// Shell commands executed around the dfmn operations
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Hooks {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pre_sync: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub post_sync: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pre_deploy: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub post_deploy: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pre_update: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub post_update: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Stage {
+    Pre,
+    Post,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Event {
+    Sync,
+    Deploy,
+    Update,
+}
+
+/// Information about the file that triggered the hook, exposed to it as env vars
+#[derive(Default)]
+pub struct Context<'a> {
+    pub file: Option<&'a str>,
+    pub destination: Option<&'a Path>,
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stage::Pre => write!(f, "pre"),
+            Stage::Post => write!(f, "post"),
+        }
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Event::Sync => write!(f, "sync"),
+            Event::Deploy => write!(f, "deploy"),
+            Event::Update => write!(f, "update"),
+        }
+    }
+}
+
+impl Hooks {
+    pub fn is_empty(&self) -> bool {
+        self.pre_sync.is_empty()
+            && self.post_sync.is_empty()
+            && self.pre_deploy.is_empty()
+            && self.post_deploy.is_empty()
+            && self.pre_update.is_empty()
+            && self.post_update.is_empty()
+    }
+
+    fn commands(&self, stage: Stage, event: Event) -> &[String] {
+        match (stage, event) {
+            (Stage::Pre, Event::Sync) => &self.pre_sync,
+            (Stage::Post, Event::Sync) => &self.post_sync,
+            (Stage::Pre, Event::Deploy) => &self.pre_deploy,
+            (Stage::Post, Event::Deploy) => &self.post_deploy,
+            (Stage::Pre, Event::Update) => &self.pre_update,
+            (Stage::Post, Event::Update) => &self.post_update,
+        }
+    }
+}
+
+fn execute(command: &str, event: Event, context: &Context) -> Result<Output, ExecutionError> {
+    let mut process = if cfg!(target_os = "windows") {
+        let mut process = Command::new("cmd");
+        process.args(["/C", command]);
+        process
+    } else {
+        let mut process = Command::new("sh");
+        process.args(["-c", command]);
+        process
+    };
+
+    process
+        .current_dir(utils::get_home_folder_path()?)
+        .env("DFMN_EVENT", event.to_string());
+
+    if let Some(file) = context.file {
+        process.env("DFMN_FILE", file);
+    }
+
+    if let Some(destination) = context.destination {
+        process.env("DFMN_DESTINATION", destination);
+    }
+
+    match process.output() {
+        Ok(output) => Ok(output),
+        Err(err) => Err(ExecutionError::Hook {
+            command: command.to_string(),
+            err: err.to_string(),
+        }),
+    }
+}
+
+fn print_output(output: &[u8]) {
+    for line in String::from_utf8_lossy(output).lines() {
+        println!("    {line}");
+    }
+}
+
+/// Runs the hooks of the given stage and event. A failing pre hook aborts the
+/// operation while a failing post hook is only reported
+pub fn run(
+    hooks: &Hooks,
+    stage: Stage,
+    event: Event,
+    context: &Context,
+) -> Result<(), CommandError> {
+    for command in hooks.commands(stage, event) {
+        println!(
+            "{} {command}",
+            format!("Running {stage}-{event} hook:").cyan()
+        );
+
+        let output = execute(command, event, context)?;
+
+        print_output(&output.stdout);
+        print_output(&output.stderr);
+
+        if output.status.success() {
+            continue;
+        }
+
+        if let Stage::Pre = stage {
+            return Err(Error::PreHookFailed {
+                stage,
+                event,
+                command: command.to_string(),
+            }
+            .into());
+        }
+
+        eprintln!(
+            "{}",
+            format!(
+                "The {stage}-{event} hook {command:?} failed with {}",
+                output.status
+            )
+            .red()
+        );
+    }
+
+    Ok(())
+}
+
+/// Runs the hooks that a tracked file declares in the manifest
+pub fn run_for_file(
+    manifest: &Manifest,
+    name: &str,
+    destination: &Path,
+    stage: Stage,
+    event: Event,
+) -> Result<(), CommandError> {
+    let Some(hooks) = manifest.file_hooks(name) else {
+        return Ok(());
+    };
+
+    run(
+        hooks,
+        stage,
+        event,
+        &Context {
+            file: Some(name),
+            destination: Some(destination),
+        },
+    )
+}
@@ -1,8 +1,18 @@
 pub mod cli;
 mod commands;
+mod config;
+mod deploy;
+mod dfmnignore;
 mod error;
 mod git;
+mod hooks;
+pub mod logger;
+mod manifest;
+mod message;
+mod paths;
+mod permissions;
 mod setup;
+mod state;
 mod utils;
 
 pub use setup::setup;
@@ -0,0 +1,264 @@
+use crate::config::{Config, LogConfig};
+use crate::error::ExecutionError;
+use crate::utils;
+use chrono::Local;
+use colored::Colorize;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::env;
+use std::error::Error;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+pub const LOG_FILE_NAME: &str = "log.txt";
+
+/// Level written in the header that starts the records of every run
+pub const RUN_LEVEL: &str = "RUN";
+
+/// Writes the records of dfmn to the log file and, depending on the verbosity,
+/// to stderr. Warnings and errors always reach the log file
+struct Logger {
+    verbosity: LevelFilter,
+    config: LogConfig,
+    invocation: String,
+    started: AtomicBool,
+}
+
+impl Logger {
+    fn file_level(&self) -> LevelFilter {
+        self.verbosity.max(LevelFilter::Warn)
+    }
+
+    /// Writes a record to the log file. The first record of a run is preceded
+    /// by a header with the version and the invocation, and the lines after the
+    /// first one are indented so every record can be told apart
+    fn write(&self, level: &str, message: &str) -> Result<(), ExecutionError> {
+        // There is nowhere to write before the dfmn folder is created, which
+        // doctor does not do
+        if !utils::get_dfm_folder_path()?.is_dir() {
+            return Ok(());
+        }
+
+        let timestamp = Local::now().format("%Y-%m-%dT%H:%M:%S%:z");
+        let mut content = String::new();
+
+        if !self.started.swap(true, Ordering::SeqCst) {
+            rotate(&self.config)?;
+
+            content.push_str(&format!(
+                "[{timestamp}] {RUN_LEVEL} dfmn {} $ {}\n",
+                env!("CARGO_PKG_VERSION"),
+                self.invocation
+            ));
+        }
+
+        let mut lines = message.lines();
+
+        content.push_str(&format!(
+            "[{timestamp}] {level} {}\n",
+            lines.next().unwrap_or_default()
+        ));
+
+        for line in lines {
+            content.push_str(format!("    {line}").trim_end());
+            content.push('\n');
+        }
+
+        let mut file = match OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(log_file_path()?)
+        {
+            Ok(file) => file,
+            Err(err) => {
+                return Err(ExecutionError::OpenFile(err.to_string()));
+            }
+        };
+
+        if let Err(err) = file.write_all(content.as_bytes()) {
+            return Err(ExecutionError::WriteToFile(err.to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        // The records of the dependencies are too noisy to be useful
+        metadata.target().starts_with(env!("CARGO_CRATE_NAME"))
+            && metadata.level() <= self.file_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let message = record.args().to_string();
+
+        if record.level() <= self.verbosity {
+            let level = match record.level() {
+                Level::Error => "error".red(),
+                Level::Warn => "warn".yellow(),
+                Level::Info => "info".cyan(),
+                Level::Debug | Level::Trace => "debug".dimmed(),
+            };
+
+            eprintln!("{level} {message}");
+        }
+
+        if let Err(err) = self.write(record.level().as_str(), &message) {
+            eprintln!(
+                "{}: {}",
+                "Error while trying to write in the log file".red(),
+                err.to_string().red()
+            );
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+fn log_file_path() -> Result<PathBuf, ExecutionError> {
+    Ok(utils::get_dfm_folder_path()?.join(LOG_FILE_NAME))
+}
+
+fn rotated_log_file_path(index: usize) -> Result<PathBuf, ExecutionError> {
+    Ok(utils::get_dfm_folder_path()?.join(format!("log.{index}.txt")))
+}
+
+/// Returns the existing log files, from the oldest to the newest
+pub fn log_file_paths() -> Result<Vec<PathBuf>, ExecutionError> {
+    let mut paths = Vec::new();
+    let mut index = 1;
+
+    loop {
+        let path = rotated_log_file_path(index)?;
+
+        if !path.exists() {
+            break;
+        }
+
+        paths.push(path);
+        index += 1;
+    }
+
+    paths.reverse();
+    paths.push(log_file_path()?);
+    paths.retain(|path| path.exists());
+
+    Ok(paths)
+}
+
+/// Moves `log.txt` to `log.1.txt`, shifting the older files, once it grows
+/// past the maximum size and removes the files beyond the maximum count
+fn rotate(config: &LogConfig) -> Result<(), ExecutionError> {
+    let log_file_path = log_file_path()?;
+
+    let Ok(metadata) = fs::metadata(&log_file_path) else {
+        return Ok(());
+    };
+
+    if metadata.len() < config.max_size * 1024 {
+        return Ok(());
+    }
+
+    let mut last = config.max_files;
+
+    while rotated_log_file_path(last + 1)?.exists() {
+        last += 1;
+    }
+
+    for index in (1..=last).rev() {
+        let path = rotated_log_file_path(index)?;
+
+        if !path.exists() {
+            continue;
+        }
+
+        let result = if index >= config.max_files {
+            fs::remove_file(path)
+        } else {
+            fs::rename(path, rotated_log_file_path(index + 1)?)
+        };
+
+        if let Err(err) = result {
+            return Err(ExecutionError::RenameFile(err.to_string()));
+        }
+    }
+
+    if config.max_files == 0 {
+        if let Err(err) = fs::remove_file(log_file_path) {
+            return Err(ExecutionError::RemoveFile(err.to_string()));
+        }
+
+        return Ok(());
+    }
+
+    if let Err(err) = fs::rename(log_file_path, rotated_log_file_path(1)?) {
+        return Err(ExecutionError::RenameFile(err.to_string()));
+    }
+
+    Ok(())
+}
+
+/// Formats an error followed by the errors that caused it
+pub fn error_chain(err: &dyn Error) -> String {
+    let mut chain = err.to_string();
+    let mut source = err.source();
+
+    while let Some(err) = source {
+        chain.push_str(&format!("\ncaused by: {err}"));
+        source = err.source();
+    }
+
+    chain
+}
+
+/// Quotes an argument for display when it is empty or has whitespace
+pub fn quote(arg: String) -> String {
+    if arg.is_empty() || arg.contains(char::is_whitespace) {
+        format!("{arg:?}")
+    } else {
+        arg
+    }
+}
+
+/// Sets up the logger, where `verbose` is the number of times the verbose flag
+/// was passed: once shows what dfmn is doing and twice also the output of git
+pub fn init(verbose: u8) {
+    let verbosity = match verbose {
+        0 => LevelFilter::Off,
+        1 => LevelFilter::Info,
+        _ => LevelFilter::Debug,
+    };
+
+    let config = Config::load().map(|config| config.log).unwrap_or_default();
+
+    let invocation = env::args()
+        .enumerate()
+        .map(|(index, arg)| {
+            if index == 0 {
+                "dfmn".to_string()
+            } else {
+                quote(arg)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let logger = Logger {
+        verbosity,
+        config,
+        invocation,
+        started: AtomicBool::new(false),
+    };
+
+    let max_level = logger.file_level();
+
+    if log::set_boxed_logger(Box::new(logger)).is_ok() {
+        log::set_max_level(max_level);
+    }
+}
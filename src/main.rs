@@ -1,14 +1,24 @@
 use colored::Colorize;
-use dfmn::{cli, setup};
+use dfmn::{cli, logger, setup};
+use std::process::ExitCode;
 
-fn main() {
-    if let Err(err) = setup() {
-        eprintln!("{}", err.to_string().red());
-
-        return;
-    }
+fn main() -> ExitCode {
+    cli::complete();
 
     let cli = cli::parse();
 
-    cli.command.invoke();
+    logger::init(cli.verbose);
+
+    if cli.command.needs_setup() {
+        if let Err(err) = setup() {
+            eprintln!("{}", err.to_string().red());
+
+            return ExitCode::from(err.exit_code());
+        }
+    }
+
+    match cli.command.invoke() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => ExitCode::from(err.exit_code()),
+    }
 }
@@ -0,0 +1,140 @@
+use crate::error::{CommandError, ExecutionError};
+use crate::hooks::Hooks;
+use crate::paths;
+use crate::utils;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const MANIFEST_FILE_NAME: &str = "dfmn.toml";
+
+/// Metadata about the tracked files, stored in the root of the repository
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Hooks executed for every file
+    #[serde(default, skip_serializing_if = "Hooks::is_empty")]
+    pub hooks: Hooks,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub files: BTreeMap<String, FileEntry>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct FileEntry {
+    /// Where the file lives on the machine, relative to the home folder or
+    /// absolute for the files outside of it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destination: Option<String>,
+    /// Octal mode of the file, restored when deploying it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    /// Modification time of the file in seconds since the Unix epoch, only
+    /// recorded when asked to preserve it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mtime: Option<i64>,
+    /// Hooks executed only for this file
+    #[serde(default, skip_serializing_if = "Hooks::is_empty")]
+    pub hooks: Hooks,
+}
+
+impl Manifest {
+    pub fn load(git_storage_folder_path: &Path) -> Result<Self, ExecutionError> {
+        let manifest_path = git_storage_folder_path.join(MANIFEST_FILE_NAME);
+
+        if !manifest_path.exists() {
+            return Ok(Manifest::default());
+        }
+
+        let content = match fs::read_to_string(manifest_path) {
+            Ok(content) => content,
+            Err(err) => {
+                return Err(ExecutionError::ReadFile(err.to_string()));
+            }
+        };
+
+        match toml::from_str(&content) {
+            Ok(manifest) => Ok(manifest),
+            Err(err) => Err(ExecutionError::ParseManifest(err.to_string())),
+        }
+    }
+
+    pub fn save(&self, git_storage_folder_path: &Path) -> Result<(), ExecutionError> {
+        let content = match toml::to_string_pretty(self) {
+            Ok(content) => content,
+            Err(err) => {
+                return Err(ExecutionError::SerializeManifest(err.to_string()));
+            }
+        };
+
+        let manifest_path = git_storage_folder_path.join(MANIFEST_FILE_NAME);
+
+        if content.is_empty() {
+            if manifest_path.exists() {
+                if let Err(err) = fs::remove_file(manifest_path) {
+                    return Err(ExecutionError::RemoveFile(err.to_string()));
+                }
+            }
+
+            return Ok(());
+        }
+
+        if let Err(err) = fs::write(manifest_path, content) {
+            return Err(ExecutionError::WriteToFile(err.to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the hooks of a tracked file, if it has any
+    pub fn file_hooks(&self, name: &str) -> Option<&Hooks> {
+        self.files.get(name).map(|entry| &entry.hooks)
+    }
+
+    /// Records where a file lives when it differs from the default destination
+    pub fn set_destination(&mut self, name: &str, path: &Path) -> Result<(), CommandError> {
+        if self.destination(name)? == path {
+            return Ok(());
+        }
+
+        let home_folder_path = utils::get_home_folder_path()?;
+
+        let destination = match path.strip_prefix(&home_folder_path) {
+            Ok(relative_path) => utils::path_to_name(relative_path)?,
+            Err(_) => match path.to_str() {
+                Some(path) => path.to_string(),
+                None => {
+                    return Err(ExecutionError::InvalidUTF8("convert Path to &str").into());
+                }
+            },
+        };
+
+        self.files.entry(name.to_string()).or_default().destination = Some(destination);
+
+        Ok(())
+    }
+
+    /// Returns the path where a tracked file should be deployed on this
+    /// machine. The manifest is shared, so the relative destinations that
+    /// leave the home folder are refused
+    pub fn destination(&self, name: &str) -> Result<PathBuf, ExecutionError> {
+        let home_folder_path = paths::normalize(&utils::get_home_folder_path()?);
+
+        let relative_destination = self
+            .files
+            .get(name)
+            .and_then(|entry| entry.destination.as_deref())
+            .unwrap_or(name);
+
+        let destination = paths::normalize(&home_folder_path.join(relative_destination));
+
+        if Path::new(relative_destination).is_relative()
+            && (!destination.starts_with(&home_folder_path) || destination == home_folder_path)
+        {
+            return Err(ExecutionError::DestinationOutsideHome(
+                relative_destination.to_string(),
+            ));
+        }
+
+        Ok(destination)
+    }
+}
@@ -0,0 +1,116 @@
+use crate::config::Config;
+use crate::error::{CommandError, ExecutionError};
+use crate::utils;
+use chrono::Local;
+use clap::Args;
+use std::fs;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Aborting the commit due to an empty commit message")]
+    EmptyMessage,
+}
+
+impl From<Error> for CommandError {
+    fn from(err: Error) -> Self {
+        CommandError::Usage(err.to_string())
+    }
+}
+
+/// Options shared by the commands that create commits
+#[derive(Debug, Default, Args)]
+pub struct MessageArgs {
+    /// Commit message, instead of the generated one
+    #[arg(short, long)]
+    pub message: Option<String>,
+    /// Opens your editor to write the commit message
+    #[arg(short, long)]
+    pub edit: bool,
+}
+
+fn render_template(template: &str, action: &str, files: &[String]) -> String {
+    let file = match files {
+        [file] => file.clone(),
+        _ => format!("{} files", files.len()),
+    };
+
+    let mut message = template
+        .replace("{action}", action)
+        .replace("{file}", &file)
+        .replace("{host}", &utils::get_host_name())
+        .replace("{user}", &utils::get_user_name())
+        .replace("{date}", &Local::now().format("%Y-%m-%d %H:%M").to_string());
+
+    if files.len() > 1 {
+        message.push('\n');
+
+        for file in files {
+            message.push_str(&format!("\n- {file}"));
+        }
+    }
+
+    message
+}
+
+fn edit_message(message: &str) -> Result<String, CommandError> {
+    let message_file_path = utils::get_dfm_folder_path()?.join("COMMIT_EDITMSG");
+
+    let content = format!(
+        "{message}\n\n# Write the commit message, the lines starting with '#' are ignored\n# and an empty message aborts the commit\n"
+    );
+
+    if let Err(err) = fs::write(&message_file_path, content) {
+        return Err(ExecutionError::WriteToFile(err.to_string()).into());
+    }
+
+    utils::open_editor(&message_file_path)?;
+
+    let content = match fs::read_to_string(&message_file_path) {
+        Ok(content) => content,
+        Err(err) => {
+            return Err(ExecutionError::ReadFile(err.to_string()).into());
+        }
+    };
+
+    let message = content
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string();
+
+    if message.is_empty() {
+        return Err(Error::EmptyMessage.into());
+    }
+
+    Ok(message)
+}
+
+impl MessageArgs {
+    /// Builds the commit message for an action over some files, using the
+    /// message passed by the user or the template from the config
+    pub fn build(&self, action: &str, files: &[String]) -> Result<String, CommandError> {
+        let config = Config::load()?;
+
+        let mut message = match &self.message {
+            Some(message) => message.clone(),
+            None => render_template(&config.commit.template, action, files),
+        };
+
+        if self.edit {
+            message = edit_message(&message)?;
+        }
+
+        if config.commit.trailers {
+            message.push_str(&format!(
+                "\n\nDfmn-Host: {}\nDfmn-User: {}",
+                utils::get_host_name(),
+                utils::get_user_name()
+            ));
+        }
+
+        Ok(message)
+    }
+}
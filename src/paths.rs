@@ -0,0 +1,274 @@
+use crate::dfmnignore::IGNORE_FILE_NAME;
+use crate::error::{CommandError, ExecutionError};
+use crate::manifest::Manifest;
+use crate::utils;
+use glob::Pattern;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::path::{Component, Path, PathBuf};
+use std::slice;
+use thiserror::Error;
+
+/// Prefix of the repository names of the files that live outside the home folder
+const OUTSIDE_HOME_PREFIX: &str = "_root";
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("{0:?} is outside your home folder, use --force to track it anyway")]
+    OutsideHome(PathBuf),
+    #[error("{0:?} is not tracked by dfmn")]
+    NotTracked(String),
+}
+
+impl From<Error> for CommandError {
+    fn from(err: Error) -> Self {
+        CommandError::Usage(err.to_string())
+    }
+}
+
+/// Removes the `.` and `..` components of a path without touching the file system
+pub fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+
+    normalized
+}
+
+/// Turns a path passed by the user, which can start with `~` or be relative to
+/// the current dir, into a normalized absolute path
+pub fn resolve(path: impl AsRef<Path>) -> Result<PathBuf, ExecutionError> {
+    let path = match path.as_ref().strip_prefix("~") {
+        Ok(path) => utils::get_home_folder_path()?.join(path),
+        Err(_) => path.as_ref().to_path_buf(),
+    };
+
+    if path.is_absolute() {
+        return Ok(normalize(&path));
+    }
+
+    match env::current_dir() {
+        Ok(current_dir) => Ok(normalize(&current_dir.join(path))),
+        Err(err) => Err(ExecutionError::GetCurrentDir(err.to_string())),
+    }
+}
+
+/// Turns a glob pattern passed by the user into an absolute pattern like
+/// `resolve` does with the paths, escaping the home or current folder that it
+/// is joined to so only the part written by the user is matched as a pattern
+pub fn resolve_pattern(pattern: &str) -> Result<String, ExecutionError> {
+    let (prefix, pattern) = if let Some(pattern) = pattern.strip_prefix("~/") {
+        (Some(utils::get_home_folder_path()?), pattern)
+    } else if Path::new(pattern).is_absolute() {
+        (None, pattern)
+    } else {
+        match env::current_dir() {
+            Ok(current_dir) => (Some(current_dir), pattern),
+            Err(err) => return Err(ExecutionError::GetCurrentDir(err.to_string())),
+        }
+    };
+
+    let path = match prefix {
+        Some(prefix) => {
+            let Some(prefix) = prefix.to_str() else {
+                return Err(ExecutionError::InvalidUTF8("convert Path to &str"));
+            };
+
+            Path::new(&Pattern::escape(prefix)).join(pattern)
+        }
+        None => PathBuf::from(pattern),
+    };
+
+    match normalize(&path).to_str() {
+        Some(path) => Ok(path.to_string()),
+        None => Err(ExecutionError::InvalidUTF8("convert Path to &str")),
+    }
+}
+
+/// Maps an absolute path to the name of the file in the repository, which is
+/// its path relative to the home folder
+pub fn get_name(path: &Path, force: bool) -> Result<String, CommandError> {
+    let home_folder_path = utils::get_home_folder_path()?;
+    let mut home_folder_paths = vec![normalize(&home_folder_path)];
+
+    if let Ok(canonical_home_folder_path) = home_folder_path.canonicalize() {
+        home_folder_paths.push(canonical_home_folder_path);
+    }
+
+    for home_folder_path in home_folder_paths {
+        if let Ok(relative_path) = path.strip_prefix(home_folder_path) {
+            if relative_path.as_os_str().is_empty() {
+                break;
+            }
+
+            return Ok(utils::path_to_name(relative_path)?);
+        }
+    }
+
+    if !force {
+        return Err(Error::OutsideHome(path.to_path_buf()).into());
+    }
+
+    let relative_path: PathBuf = path
+        .components()
+        .filter(|component| matches!(component, Component::Normal(_)))
+        .collect();
+
+    Ok(utils::path_to_name(
+        &Path::new(OUTSIDE_HOME_PREFIX).join(relative_path),
+    )?)
+}
+
+/// Resolves the arguments of the commands that work on tracked files, which can
+/// be repository names, paths to the files or glob patterns over the names.
+/// Returns the name and the live path of every file
+pub fn resolve_tracked(
+    git_storage_folder_path: &Path,
+    manifest: &Manifest,
+    args: &[String],
+) -> Result<Vec<(String, PathBuf)>, CommandError> {
+    resolve_args(git_storage_folder_path, manifest, args, false)
+}
+
+/// Like `resolve_tracked`, but keeps the names that are not tracked anymore as
+/// they are, for the files that only exist in past revisions
+pub fn resolve_names(
+    git_storage_folder_path: &Path,
+    manifest: &Manifest,
+    args: &[String],
+) -> Result<Vec<(String, PathBuf)>, CommandError> {
+    resolve_args(git_storage_folder_path, manifest, args, true)
+}
+
+fn resolve_args(
+    git_storage_folder_path: &Path,
+    manifest: &Manifest,
+    args: &[String],
+    keep_untracked: bool,
+) -> Result<Vec<(String, PathBuf)>, CommandError> {
+    let tracked_files = utils::get_tracked_files(git_storage_folder_path)?;
+    let mut names_by_destination = HashMap::new();
+
+    for name in &tracked_files {
+        names_by_destination.insert(manifest.destination(name)?, name.clone());
+    }
+
+    let mut files = Vec::new();
+    let mut seen = HashSet::new();
+
+    for arg in args {
+        if utils::is_glob_pattern(arg) {
+            for name in
+                utils::expand_tracked_patterns(git_storage_folder_path, slice::from_ref(arg))?
+            {
+                let destination = manifest.destination(&name)?;
+
+                files.push((name, destination));
+            }
+
+            continue;
+        }
+
+        let path = resolve(arg)?;
+
+        if let Some(name) = names_by_destination.get(&path) {
+            files.push((name.clone(), path));
+        } else if keep_untracked
+            || tracked_files.contains(arg)
+            || (arg == IGNORE_FILE_NAME && git_storage_folder_path.join(arg).is_file())
+        {
+            files.push((arg.clone(), manifest.destination(arg)?));
+        } else {
+            return Err(Error::NotTracked(arg.clone()).into());
+        }
+    }
+
+    files.retain(|(name, _)| seen.insert(name.clone()));
+
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every test sets the same home folder, with glob syntax in it, so they
+    /// can run in parallel
+    const HOME: &str = "/home/user[1]";
+
+    fn set_home() {
+        env::set_var("HOME", HOME);
+    }
+
+    #[test]
+    fn normalize_removes_the_dot_components() {
+        assert_eq!(normalize(Path::new("/a/./b/../c")), Path::new("/a/c"));
+        assert_eq!(normalize(Path::new("/a/b/../../..")), Path::new("/"));
+        assert_eq!(normalize(Path::new("a/../../b")), Path::new("b"));
+        assert_eq!(normalize(Path::new("/a/[b]/*")), Path::new("/a/[b]/*"));
+    }
+
+    #[test]
+    fn get_name_is_relative_to_home() {
+        set_home();
+
+        assert_eq!(
+            get_name(Path::new("/home/user[1]/.zshrc"), false).unwrap(),
+            ".zshrc"
+        );
+        assert_eq!(
+            get_name(Path::new("/home/user[1]/.config/nvim/init.lua"), false).unwrap(),
+            ".config/nvim/init.lua"
+        );
+    }
+
+    #[test]
+    fn get_name_refuses_the_paths_outside_home_unless_forced() {
+        set_home();
+
+        assert!(get_name(Path::new("/etc/hosts"), false).is_err());
+        assert!(get_name(Path::new(HOME), false).is_err());
+        assert_eq!(
+            get_name(Path::new("/etc/hosts"), true).unwrap(),
+            "_root/etc/hosts"
+        );
+    }
+
+    #[test]
+    fn resolve_pattern_escapes_the_home_folder() {
+        set_home();
+
+        assert_eq!(
+            resolve_pattern("~/.config/*").unwrap(),
+            "/home/user[[]1[]]/.config/*"
+        );
+        assert_eq!(
+            resolve_pattern("~/a/../.*rc").unwrap(),
+            "/home/user[[]1[]]/.*rc"
+        );
+    }
+
+    #[test]
+    fn resolve_pattern_escapes_the_current_dir() {
+        let current_dir = env::current_dir().unwrap();
+        let current_dir = Pattern::escape(current_dir.to_str().unwrap());
+
+        assert_eq!(
+            resolve_pattern("*.rs").unwrap(),
+            format!("{current_dir}/*.rs")
+        );
+    }
+
+    #[test]
+    fn resolve_pattern_keeps_the_absolute_patterns() {
+        assert_eq!(resolve_pattern("/etc/[ab]*").unwrap(), "/etc/[ab]*");
+    }
+}
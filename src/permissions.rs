@@ -0,0 +1,190 @@
+use crate::error::ExecutionError;
+use crate::manifest::FileEntry;
+use colored::Colorize;
+use filetime::FileTime;
+use std::fs::{self, File};
+use std::io;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+/// Folders whose files should only be readable by their owner
+const SENSITIVE_FOLDERS: [&str; 2] = [".ssh", ".gnupg"];
+
+fn get_metadata(path: &Path) -> Result<fs::Metadata, ExecutionError> {
+    match fs::metadata(path) {
+        Ok(metadata) => Ok(metadata),
+        Err(err) => Err(ExecutionError::GetMetadata(err.to_string())),
+    }
+}
+
+#[cfg(unix)]
+fn get_mode(path: &Path) -> Result<Option<u32>, ExecutionError> {
+    use std::os::unix::fs::PermissionsExt;
+
+    Ok(Some(get_metadata(path)?.permissions().mode() & 0o7777))
+}
+
+#[cfg(not(unix))]
+fn get_mode(_path: &Path) -> Result<Option<u32>, ExecutionError> {
+    Ok(None)
+}
+
+#[cfg(unix)]
+fn set_mode(path: &Path, mode: u32) -> Result<(), ExecutionError> {
+    use std::os::unix::fs::PermissionsExt;
+
+    if let Err(err) = fs::set_permissions(path, fs::Permissions::from_mode(mode)) {
+        return Err(ExecutionError::SetPermissions(err.to_string()));
+    }
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_mode(_path: &Path, _mode: u32) -> Result<(), ExecutionError> {
+    Ok(())
+}
+
+/// Copies a file, giving the destination its mode before writing the content so
+/// a private file is never readable by other users, not even for a moment.
+/// Without a mode the one of the source is kept, like `fs::copy` does
+#[cfg(unix)]
+pub fn copy_file(
+    source: &Path,
+    destination: &Path,
+    mode: Option<u32>,
+) -> Result<(), ExecutionError> {
+    use std::os::unix::fs::OpenOptionsExt;
+
+    let mode = match mode {
+        Some(mode) => mode,
+        None => get_mode(source)?.unwrap_or(0o644),
+    };
+
+    // The owner needs to write the content even if the mode is read only, the
+    // final mode is set once it is written
+    let writable_mode = mode | 0o600;
+
+    // Opening a file that exists does not change its mode
+    if destination.exists() {
+        set_mode(destination, writable_mode)?;
+    }
+
+    let mut source_file = match File::open(source) {
+        Ok(file) => file,
+        Err(err) => {
+            return Err(ExecutionError::ReadFile(err.to_string()));
+        }
+    };
+
+    let mut destination_file = match fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(writable_mode)
+        .open(destination)
+    {
+        Ok(file) => file,
+        Err(err) => {
+            return Err(ExecutionError::CopyFile(err.to_string()));
+        }
+    };
+
+    if let Err(err) = io::copy(&mut source_file, &mut destination_file) {
+        return Err(ExecutionError::CopyFile(err.to_string()));
+    }
+
+    set_mode(destination, mode)
+}
+
+#[cfg(not(unix))]
+pub fn copy_file(
+    source: &Path,
+    destination: &Path,
+    _mode: Option<u32>,
+) -> Result<(), ExecutionError> {
+    if let Err(err) = fs::copy(source, destination) {
+        return Err(ExecutionError::CopyFile(err.to_string()));
+    }
+
+    Ok(())
+}
+
+fn is_sensitive(path: &Path) -> bool {
+    path.components().any(|component| {
+        SENSITIVE_FOLDERS
+            .iter()
+            .any(|folder| component.as_os_str() == *folder)
+    })
+}
+
+/// Records the mode of a live file and its modification time, when asked to or
+/// when it was already being recorded
+pub fn record(
+    entry: &mut FileEntry,
+    path: &Path,
+    preserve_mtime: bool,
+) -> Result<(), ExecutionError> {
+    entry.mode = get_mode(path)?.map(|mode| format!("{mode:o}"));
+
+    if !preserve_mtime && entry.mtime.is_none() {
+        return Ok(());
+    }
+
+    let modified = match get_metadata(path)?.modified() {
+        Ok(modified) => modified,
+        Err(err) => {
+            return Err(ExecutionError::GetMetadata(err.to_string()));
+        }
+    };
+
+    entry.mtime = modified
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|duration| duration.as_secs() as i64);
+
+    Ok(())
+}
+
+/// Returns the mode recorded for a file, if there is one
+pub fn get_recorded_mode(entry: Option<&FileEntry>) -> Option<u32> {
+    entry
+        .and_then(|entry| entry.mode.as_deref())
+        .and_then(|mode| u32::from_str_radix(mode, 8).ok())
+}
+
+/// Applies the recorded mode to the repository copy of a file, which git
+/// checks out with its default mode
+pub fn restore_mode(entry: Option<&FileEntry>, path: &Path) -> Result<(), ExecutionError> {
+    match get_recorded_mode(entry) {
+        Some(mode) => set_mode(path, mode),
+        None => Ok(()),
+    }
+}
+
+/// Applies the recorded mode and modification time to a deployed file, warning
+/// when a sensitive file ends up readable by other users
+pub fn restore(entry: Option<&FileEntry>, path: &Path) -> Result<(), ExecutionError> {
+    restore_mode(entry, path)?;
+
+    if let Some(mtime) = entry.and_then(|entry| entry.mtime) {
+        if let Err(err) = filetime::set_file_mtime(path, FileTime::from_unix_time(mtime, 0)) {
+            return Err(ExecutionError::SetModificationTime(err.to_string()));
+        }
+    }
+
+    if let Some(mode) = get_mode(path)? {
+        if is_sensitive(path) && mode & 0o077 != 0 {
+            eprintln!(
+                "{}",
+                format!(
+                    "Warning: {} is sensitive but has the mode {mode:o}, consider running chmod 600 on it",
+                    path.display()
+                )
+                .yellow()
+            );
+        }
+    }
+
+    Ok(())
+}
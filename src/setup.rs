@@ -14,7 +14,7 @@ pub enum Error {
 
 impl From<Error> for CommandError {
     fn from(err: Error) -> Self {
-        CommandError::Usage(err.to_string())
+        CommandError::Git(err.to_string())
     }
 }
 
@@ -35,7 +35,11 @@ fn check_if_git_is_installed() -> Result<(), CommandError> {
     };
 
     let Some(command_code) = status.code() else {
-        return Err(ExecutionError::Unknown { err: "process terminated by signal".to_string(), trying_to: "get git status code" }.into());
+        return Err(ExecutionError::Unknown {
+            err: "process terminated by signal".to_string(),
+            trying_to: "get git status code",
+        }
+        .into());
     };
 
     if command_code != 1 {
@@ -0,0 +1,200 @@
+use crate::error::ExecutionError;
+use crate::git;
+use crate::utils;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+pub const STATE_FILE_NAME: &str = "state.toml";
+
+/// What this machine last deployed or committed of every tracked file, stored
+/// in the dfmn folder since it differs between machines
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct State {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub files: BTreeMap<String, FileState>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileState {
+    /// git hash of the content that the live file and the repository had in
+    /// common the last time
+    pub hash: String,
+}
+
+/// Which side changed a file since it was last deployed or committed
+#[derive(Debug, PartialEq, Eq)]
+pub enum Change {
+    /// The live file and the repository have the same content
+    InSync,
+    /// Only the live file changed
+    Local,
+    /// Only the repository changed
+    Remote,
+    /// Both changed, so neither can overwrite the other
+    Both,
+    /// Nothing was recorded for the file
+    Unknown,
+    /// The live file does not exist, so deploying it loses nothing
+    Missing,
+}
+
+impl State {
+    pub fn load() -> Result<Self, ExecutionError> {
+        let state_path = utils::get_dfm_folder_path()?.join(STATE_FILE_NAME);
+
+        if !state_path.exists() {
+            return Ok(State::default());
+        }
+
+        let content = match fs::read_to_string(state_path) {
+            Ok(content) => content,
+            Err(err) => {
+                return Err(ExecutionError::ReadFile(err.to_string()));
+            }
+        };
+
+        match toml::from_str(&content) {
+            Ok(state) => Ok(state),
+            Err(err) => Err(ExecutionError::ParseState(err.to_string())),
+        }
+    }
+
+    pub fn save(&self) -> Result<(), ExecutionError> {
+        let content = match toml::to_string_pretty(self) {
+            Ok(content) => content,
+            Err(err) => {
+                return Err(ExecutionError::SerializeState(err.to_string()));
+            }
+        };
+
+        let state_path = utils::get_dfm_folder_path()?.join(STATE_FILE_NAME);
+
+        if let Err(err) = fs::write(state_path, content) {
+            return Err(ExecutionError::WriteToFile(err.to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the hash recorded for a file, if there is one
+    pub fn hash(&self, name: &str) -> Option<&str> {
+        self.files.get(name).map(|file| file.hash.as_str())
+    }
+
+    /// Records the content of a file that was just deployed or committed
+    pub fn record(
+        &mut self,
+        git_storage_folder_path: &Path,
+        name: &str,
+        path: &Path,
+    ) -> Result<(), ExecutionError> {
+        let hash = git::hash_file(git_storage_folder_path, path)?;
+
+        self.files.insert(name.to_string(), FileState { hash });
+
+        Ok(())
+    }
+
+    pub fn forget(&mut self, name: &str) {
+        self.files.remove(name);
+    }
+
+    pub fn rename(&mut self, old_name: &str, new_name: &str) {
+        if let Some(file) = self.files.remove(old_name) {
+            self.files.insert(new_name.to_string(), file);
+        }
+    }
+}
+
+/// Loads the state, records a file and saves it, for the commands that handle
+/// one file at a time
+pub fn record(
+    git_storage_folder_path: &Path,
+    name: &str,
+    path: &Path,
+) -> Result<(), ExecutionError> {
+    let mut state = State::load()?;
+
+    state.record(git_storage_folder_path, name, path)?;
+    state.save()
+}
+
+/// Compares the live file and the repository version of a file with the
+/// content they had in common, `base`, to tell which one changed
+pub fn classify(
+    git_storage_folder_path: &Path,
+    base: Option<&str>,
+    name: &str,
+    live_file_path: &Path,
+) -> Result<Change, ExecutionError> {
+    let hash = |path: &Path| -> Result<Option<String>, ExecutionError> {
+        if path.is_file() {
+            Ok(Some(git::hash_file(git_storage_folder_path, path)?))
+        } else {
+            Ok(None)
+        }
+    };
+
+    let Some(live) = hash(live_file_path)? else {
+        return Ok(Change::Missing);
+    };
+
+    let stored = hash(&git_storage_folder_path.join(name))?;
+
+    Ok(compare(base, &live, stored.as_deref()))
+}
+
+/// Tells which side changed from the hashes of the content in common, the live
+/// file and the repository version, which is missing when it was removed
+fn compare(base: Option<&str>, live: &str, stored: Option<&str>) -> Change {
+    if Some(live) == stored {
+        return Change::InSync;
+    }
+
+    let Some(base) = base else {
+        return Change::Unknown;
+    };
+
+    if live == base {
+        Change::Remote
+    } else if stored == Some(base) {
+        Change::Local
+    } else {
+        Change::Both
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compare_same_content_is_in_sync() {
+        assert_eq!(compare(None, "a", Some("a")), Change::InSync);
+        assert_eq!(compare(Some("b"), "a", Some("a")), Change::InSync);
+    }
+
+    #[test]
+    fn compare_without_base_is_unknown() {
+        assert_eq!(compare(None, "a", Some("b")), Change::Unknown);
+        assert_eq!(compare(None, "a", None), Change::Unknown);
+    }
+
+    #[test]
+    fn compare_tells_which_side_changed() {
+        assert_eq!(
+            compare(Some("base"), "base", Some("remote")),
+            Change::Remote
+        );
+        assert_eq!(compare(Some("base"), "local", Some("base")), Change::Local);
+        assert_eq!(compare(Some("base"), "local", Some("remote")), Change::Both);
+    }
+
+    #[test]
+    fn compare_removed_from_the_repository() {
+        assert_eq!(compare(Some("base"), "base", None), Change::Remote);
+        assert_eq!(compare(Some("base"), "local", None), Change::Both);
+    }
+}
@@ -1,14 +1,24 @@
+use crate::dfmnignore::{IgnoreMatcher, IGNORE_FILE_NAME};
 use crate::error::{CommandError, ExecutionError};
+use crate::manifest::MANIFEST_FILE_NAME;
+use glob::{MatchOptions, Pattern};
+use std::collections::HashSet;
 use std::env;
-use std::fs::{self, File};
-use std::io::Write;
+use std::fs::File;
+use std::io::{self, BufReader, IsTerminal, Read, Write};
 use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 use thiserror::Error;
+use walkdir::WalkDir;
 
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("You need to set a remote repository before use dfmn")]
     SetRemoteRepository,
+    #[error("Invalid glob pattern {pattern:?}: {err}")]
+    InvalidPattern { pattern: String, err: String },
+    #[error("Nothing matches the pattern {0:?}")]
+    NoMatches(String),
 }
 
 impl From<Error> for CommandError {
@@ -45,6 +55,22 @@ pub fn get_dfm_folder_path() -> Result<PathBuf, ExecutionError> {
     }
 }
 
+pub fn get_home_folder_path() -> Result<PathBuf, ExecutionError> {
+    let name = if cfg!(any(target_os = "linux", target_os = "macos")) {
+        "HOME"
+    } else {
+        "USERPROFILE"
+    };
+
+    match env::var(name) {
+        Ok(env) => Ok(PathBuf::from(env)),
+        Err(err) => Err(ExecutionError::GetEnvVar {
+            name,
+            err: err.to_string(),
+        }),
+    }
+}
+
 pub fn get_git_storage_folder_path() -> Result<PathBuf, ExecutionError> {
     Ok(get_dfm_folder_path()?.join("dotfiles"))
 }
@@ -53,6 +79,241 @@ pub fn check_if_file_exists(folder: &Path, file_name: &str) -> bool {
     folder.join(file_name).exists()
 }
 
+pub fn check_if_files_are_equal(first_file: &Path, second_file: &Path) -> Result<bool, io::Error> {
+    let first_file = File::open(first_file)?;
+    let second_file = File::open(second_file)?;
+
+    let first_file_metadata = first_file.metadata()?;
+    let second_file_metadata = second_file.metadata()?;
+
+    if first_file_metadata.len() != second_file_metadata.len() {
+        return Ok(false);
+    }
+
+    let first_file_reader = BufReader::new(first_file);
+    let second_file_reader = BufReader::new(second_file);
+
+    for (b1, b2) in first_file_reader.bytes().zip(second_file_reader.bytes()) {
+        if b1? != b2? {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Converts a path relative to the repository root to the name used to identify the file
+pub fn path_to_name(path: &Path) -> Result<String, ExecutionError> {
+    let mut components = Vec::new();
+
+    for component in path.components() {
+        let Some(component) = component.as_os_str().to_str() else {
+            return Err(ExecutionError::InvalidUTF8("convert OsStr to &str"));
+        };
+
+        components.push(component);
+    }
+
+    Ok(components.join("/"))
+}
+
+/// Returns the names of all the files tracked in the repository, sorted and
+/// without the internal and ignored ones
+pub fn get_tracked_files(git_storage_folder_path: &Path) -> Result<Vec<String>, ExecutionError> {
+    let ignore_matcher = IgnoreMatcher::load(git_storage_folder_path)?;
+    let mut files = Vec::new();
+
+    let walker = WalkDir::new(git_storage_folder_path)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|entry| entry.depth() != 1 || entry.file_name() != ".git");
+
+    for entry in walker {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(err) => {
+                return Err(ExecutionError::GetDirEntry(err.to_string()));
+            }
+        };
+
+        if entry.file_type().is_dir() {
+            continue;
+        }
+
+        let Ok(relative_path) = entry.path().strip_prefix(git_storage_folder_path) else {
+            continue;
+        };
+
+        let name = path_to_name(relative_path)?;
+
+        if name == MANIFEST_FILE_NAME
+            || name == IGNORE_FILE_NAME
+            || ignore_matcher.is_ignored(&name, false)
+        {
+            continue;
+        }
+
+        files.push(name);
+    }
+
+    files.sort();
+
+    Ok(files)
+}
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+pub fn is_glob_pattern(name: &str) -> bool {
+    name.contains(['*', '?', '['])
+}
+
+fn compile_pattern(pattern: &str) -> Result<Pattern, Error> {
+    match Pattern::new(pattern) {
+        Ok(pattern) => Ok(pattern),
+        Err(err) => Err(Error::InvalidPattern {
+            pattern: pattern.to_string(),
+            err: err.to_string(),
+        }),
+    }
+}
+
+fn push_unique(names: &mut Vec<String>, seen: &mut HashSet<String>, name: String) {
+    if seen.insert(name.clone()) {
+        names.push(name);
+    }
+}
+
+/// Expands the glob patterns against the tracked files, keeping the plain names as they are
+pub fn expand_tracked_patterns(
+    git_storage_folder_path: &Path,
+    patterns: &[String],
+) -> Result<Vec<String>, CommandError> {
+    let mut names = Vec::new();
+    let mut seen = HashSet::new();
+    let mut tracked_files = None;
+
+    for pattern in patterns {
+        if !is_glob_pattern(pattern) {
+            push_unique(&mut names, &mut seen, pattern.clone());
+
+            continue;
+        }
+
+        let compiled_pattern = compile_pattern(pattern)?;
+
+        if tracked_files.is_none() {
+            tracked_files = Some(get_tracked_files(git_storage_folder_path)?);
+        }
+
+        let matches: Vec<&String> = tracked_files
+            .iter()
+            .flatten()
+            .filter(|name| compiled_pattern.matches_with(name, MATCH_OPTIONS))
+            .collect();
+
+        if matches.is_empty() {
+            return Err(Error::NoMatches(pattern.clone()).into());
+        }
+
+        for name in matches {
+            push_unique(&mut names, &mut seen, name.clone());
+        }
+    }
+
+    Ok(names)
+}
+
+/// Keeps the names that match any of the glob patterns, where a plain name
+/// matches the file itself and every file inside the folder it names
+pub fn filter_tracked_files(
+    names: Vec<String>,
+    patterns: &[String],
+) -> Result<Vec<String>, CommandError> {
+    if patterns.is_empty() {
+        return Ok(names);
+    }
+
+    let mut compiled_patterns = Vec::new();
+
+    for pattern in patterns.iter().filter(|pattern| is_glob_pattern(pattern)) {
+        compiled_patterns.push(compile_pattern(pattern)?);
+    }
+
+    Ok(names
+        .into_iter()
+        .filter(|name| {
+            compiled_patterns
+                .iter()
+                .any(|pattern| pattern.matches_with(name, MATCH_OPTIONS))
+                || patterns.iter().any(|pattern| {
+                    let folder = pattern.trim_end_matches('/');
+
+                    name == pattern
+                        || name
+                            .strip_prefix(folder)
+                            .is_some_and(|rest| rest.starts_with('/'))
+                })
+        })
+        .collect())
+}
+
+/// Expands the glob pattern of an absolute path against the file system
+pub fn expand_path_pattern(pattern: &str) -> Result<Vec<PathBuf>, CommandError> {
+    let matches = match glob::glob_with(pattern, MATCH_OPTIONS) {
+        Ok(matches) => matches,
+        Err(err) => {
+            return Err(Error::InvalidPattern {
+                pattern: pattern.to_string(),
+                err: err.to_string(),
+            }
+            .into());
+        }
+    };
+
+    let mut paths = Vec::new();
+
+    for path in matches {
+        match path {
+            Ok(path) => paths.push(path),
+            Err(err) => {
+                return Err(ExecutionError::GetDirEntry(err.to_string()).into());
+            }
+        }
+    }
+
+    if paths.is_empty() {
+        return Err(Error::NoMatches(pattern.to_string()).into());
+    }
+
+    Ok(paths)
+}
+
+/// Prints the question and returns the trimmed and lowercased answer of the
+/// user, or `None` when the input is closed and nobody can answer
+pub fn prompt(question: &str) -> Result<Option<String>, ExecutionError> {
+    print!("{question} ");
+
+    if let Err(err) = io::stdout().flush() {
+        return Err(ExecutionError::WriteToFile(err.to_string()));
+    }
+
+    let mut answer = String::new();
+
+    match io::stdin().read_line(&mut answer) {
+        Ok(0) => {
+            println!();
+
+            Ok(None)
+        }
+        Ok(_) => Ok(Some(answer.trim().to_lowercase())),
+        Err(err) => Err(ExecutionError::ReadInput(err.to_string())),
+    }
+}
+
 pub fn check_if_remote_link_is_added() -> Result<(), CommandError> {
     let dfm_folder_path = get_dfm_folder_path()?;
 
@@ -63,31 +324,166 @@ pub fn check_if_remote_link_is_added() -> Result<(), CommandError> {
     Ok(())
 }
 
-pub fn write_to_log_file(content: &str) -> Result<(), ExecutionError> {
-    let dfm_folder_path = get_dfm_folder_path()?;
+pub fn get_host_name() -> String {
+    for name in ["HOSTNAME", "COMPUTERNAME"] {
+        if let Ok(host_name) = env::var(name) {
+            return host_name;
+        }
+    }
 
-    let mut file = if !dfm_folder_path.join("log.txt").exists() {
-        match File::create(dfm_folder_path.join("log.txt")) {
-            Ok(file) => file,
-            Err(err) => {
-                return Err(ExecutionError::CreateFile(err.to_string()));
+    if let Ok(output) = Command::new("hostname").output() {
+        if let Ok(host_name) = String::from_utf8(output.stdout) {
+            if output.status.success() && !host_name.trim().is_empty() {
+                return host_name.trim().to_string();
             }
         }
-    } else {
-        match fs::OpenOptions::new()
-            .append(true)
-            .open(dfm_folder_path.join("log.txt"))
-        {
-            Ok(file) => file,
-            Err(err) => {
-                return Err(ExecutionError::OpenFile(err.to_string()));
+    }
+
+    "unknown".to_string()
+}
+
+pub fn get_user_name() -> String {
+    for name in ["USER", "USERNAME"] {
+        if let Ok(user_name) = env::var(name) {
+            return user_name;
+        }
+    }
+
+    "unknown".to_string()
+}
+
+/// Opens a file in the editor of the user and waits for it to be closed
+pub fn open_editor(file_path: &Path) -> Result<(), ExecutionError> {
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| {
+            if cfg!(target_os = "windows") {
+                "notepad".to_string()
+            } else {
+                "vi".to_string()
             }
+        });
+
+    let mut words = editor.split_whitespace();
+
+    let Some(program) = words.next() else {
+        return Err(ExecutionError::OpenEditor("empty editor".to_string()));
+    };
+
+    let status = match Command::new(program).args(words).arg(file_path).status() {
+        Ok(status) => status,
+        Err(err) => {
+            return Err(ExecutionError::OpenEditor(err.to_string()));
         }
     };
 
-    if let Err(err) = writeln!(file, "{content}") {
-        return Err(ExecutionError::WriteToFile(err.to_string()));
+    if !status.success() {
+        return Err(ExecutionError::OpenEditor(format!(
+            "{editor} exited with {status}"
+        )));
     }
 
     Ok(())
 }
+
+/// Shows some content in the pager of the user, or prints it when the output is
+/// not a terminal. The file name is passed to bat so it can highlight the syntax
+pub fn page(content: &[u8], file_name: &str) -> Result<(), ExecutionError> {
+    let mut stdout = io::stdout();
+
+    let pager = env::var("PAGER").unwrap_or_else(|_| {
+        if cfg!(target_os = "windows") {
+            "more".to_string()
+        } else {
+            "less".to_string()
+        }
+    });
+
+    let mut words = pager.split_whitespace();
+
+    let Some(program) = words.next().filter(|_| stdout.is_terminal()) else {
+        if let Err(err) = stdout.write_all(content) {
+            return Err(ExecutionError::WriteToFile(err.to_string()));
+        }
+
+        return Ok(());
+    };
+
+    let mut command = Command::new(program);
+    command.args(words).stdin(Stdio::piped());
+
+    if matches!(
+        Path::new(program)
+            .file_stem()
+            .and_then(|stem| stem.to_str()),
+        Some("bat" | "batcat")
+    ) {
+        command.args(["--file-name", file_name]);
+    }
+
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(err) => {
+            return Err(ExecutionError::OpenPager(err.to_string()));
+        }
+    };
+
+    // The pager closes its input when it is quit before reading everything
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(content);
+    }
+
+    if let Err(err) = child.wait() {
+        return Err(ExecutionError::OpenPager(err.to_string()));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names() -> Vec<String> {
+        [
+            ".bashrc",
+            ".config/nvim/init.lua",
+            ".config/nvim-old/init.lua",
+            ".zshrc",
+        ]
+        .map(String::from)
+        .to_vec()
+    }
+
+    fn filter(patterns: &[&str]) -> Vec<String> {
+        let patterns: Vec<String> = patterns.iter().map(|pattern| pattern.to_string()).collect();
+
+        filter_tracked_files(names(), &patterns).unwrap()
+    }
+
+    #[test]
+    fn filter_tracked_files_keeps_everything_without_patterns() {
+        assert_eq!(filter(&[]), names());
+    }
+
+    #[test]
+    fn filter_tracked_files_matches_globs_and_names() {
+        assert_eq!(filter(&[".*rc"]), [".bashrc", ".zshrc"]);
+        assert_eq!(filter(&[".zshrc"]), [".zshrc"]);
+        assert_eq!(
+            filter(&[".config"]),
+            [".config/nvim/init.lua", ".config/nvim-old/init.lua"]
+        );
+    }
+
+    #[test]
+    fn filter_tracked_files_matches_whole_folders() {
+        assert_eq!(filter(&[".config/nvim"]), [".config/nvim/init.lua"]);
+        assert_eq!(filter(&[".config/nvim/"]), [".config/nvim/init.lua"]);
+    }
+
+    #[test]
+    fn filter_tracked_files_does_not_cross_folders_with_globs() {
+        assert!(filter(&[".config/*.lua"]).is_empty());
+    }
+}